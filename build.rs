@@ -1,82 +1,165 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::env;
+use std::fs;
 use std::fs::read_dir;
-use std::fs::DirEntry;
-use std::fs::File;
-use std::io::LineWriter;
-use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 
-/// Build the file that allows us to dynamically select a solution function.
-/// We always rerun this because compilation of this file should be cheap.
+/// Build the file that declares every year and day module and registers their solutions.
+///
+/// The script reruns when a year directory changes, and when `src` itself changes so that new
+/// year directories are noticed. Cargo scans `src` recursively, so other source edits rerun it
+/// too, but that is cheap: the output is sorted so it is the same for the same tree, and it is
+/// only rewritten when it changes, so an unchanged tree does not trigger a rebuild.
 fn main() {
     let out_dir = env::var_os("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("module_selection.rs");
+    let src_dir = Path::new(&env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("src");
 
-    let lookup_insert_statements = year_modules_to_day_modules()
-        .into_iter()
-        .flat_map(|(year_module, day_modules)| {
-            day_modules.into_iter().map(move |day| {
-                let key = format!("\"{year_module}::{day}\".to_string()");
-                let value = format!("Box::new({year_module}::{day}::solve)");
-                format!("    selector.insert({key}, {value});")
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src");
+    let years = match find_years(&src_dir) {
+        Ok(years) => years,
+        Err(mut problems) => {
+            problems.sort();
+            panic!(
+                "Cannot generate the solution registry:\n  {}",
+                problems.join("\n  ")
+            )
+        }
+    };
+    for year in years.keys() {
+        println!("cargo:rerun-if-changed=src/year{year}");
+    }
+
+    let module_tree = years
+        .iter()
+        .map(|(year, days)| year_module(*year, days))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let registry_entries = years
+        .iter()
+        .flat_map(|(year, days)| {
+            days.keys().map(move |day| {
+                let solution = format!("year{year}::day{day}::Solution");
+                format!(
+                    "    Registration {{ year: {year}, day: {day}, solver: || Box::<{solution}>::default() }},"
+                )
             })
         })
         .collect::<Vec<String>>()
         .join("\n");
 
-    // I am not proud of this but it works.
-    let mut line_writer = LineWriter::new(File::create(dest_path).unwrap());
-    let lines = vec![
-        "pub fn solution_selector() -> HashMap<String, Box<dyn Fn(PathBuf)>> {\n",
-        "    let mut selector: HashMap<String, Box<dyn Fn(PathBuf)>> = HashMap::new();\n",
-        &lookup_insert_statements,
-        "\n",
-        "    selector\n",
-        "}\n",
-    ];
-    for line in lines.into_iter() {
-        line_writer.write_all(line.as_bytes()).unwrap();
+    let generated = format!(
+        "{module_tree}\n\
+        /// Every solution found by `build.rs`, sorted by year and then day.\n\
+        pub const SOLUTIONS: &[Registration] = &[\n\
+        {registry_entries}\n\
+        ];\n"
+    );
+    // Leave an unchanged file alone so its modification time does not trigger a rebuild.
+    if fs::read_to_string(&dest_path).ok().as_deref() != Some(generated.as_str()) {
+        fs::write(&dest_path, generated).unwrap();
     }
 }
 
-fn year_modules_to_day_modules() -> HashMap<String, Vec<String>> {
-    read_dir("src")
-        .unwrap()
-        .into_iter()
-        .filter_map(some_if_year_module)
-        .map(|(year_module, year_module_path)| {
-            (year_module, day_modules_for_year(year_module_path))
-        })
-        .collect()
+/// Declare a year module with each day module pointing at its source file.
+fn year_module(year: u16, days: &BTreeMap<u8, PathBuf>) -> String {
+    let day_modules = days
+        .iter()
+        .map(|(day, path)| format!("    #[path = {path:?}]\n    pub mod day{day};\n"))
+        .collect::<String>();
+    format!("pub mod year{year} {{\n{day_modules}}}\n")
 }
 
-fn some_if_year_module(entry: Result<DirEntry, std::io::Error>) -> Option<(String, PathBuf)> {
-    entry.map_or(None, |entry| {
-        is_year_module(&entry).then(|| (entry_file_name(&entry), entry.path()))
-    })
-}
+/// Find every `src/yearYYYY` directory and the days within it, sorted by year and day.
+/// Returns every problem found if any file names are malformed or any days are duplicated.
+fn find_years(src_dir: &Path) -> Result<BTreeMap<u16, BTreeMap<u8, PathBuf>>, Vec<String>> {
+    let mut years = BTreeMap::new();
+    let mut problems = Vec::new();
+    for entry in read_dir(src_dir).unwrap().map(Result::unwrap) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !entry.path().is_dir() || !name.starts_with("year") {
+            continue;
+        }
+        match parse_number(&name, "year", 2015..=9999) {
+            Ok(year) => {
+                years.insert(year, find_days(&entry.path(), &mut problems));
+            }
+            Err(problem) => problems.push(format!("{}: {problem}", entry.path().display())),
+        }
+    }
 
-fn entry_file_name(entry: &DirEntry) -> String {
-    entry.file_name().to_str().unwrap().to_owned()
+    if problems.is_empty() {
+        Ok(years)
+    } else {
+        Err(problems)
+    }
 }
 
-fn is_year_module(entry: &DirEntry) -> bool {
-    entry.path().is_dir() && entry.file_name().to_str().unwrap().starts_with("year")
-}
+/// Find the days in a year directory. Each day is either `dayN.rs` or `dayN/mod.rs`.
+fn find_days(year_dir: &Path, problems: &mut Vec<String>) -> BTreeMap<u8, PathBuf> {
+    let mut days = BTreeMap::new();
+    for entry in read_dir(year_dir).unwrap().map(Result::unwrap) {
+        let path = entry.path();
+        let (name, module_path) = if path.is_dir() {
+            (
+                entry.file_name().to_string_lossy().into_owned(),
+                path.join("mod.rs"),
+            )
+        } else if let Some(stem) = entry.file_name().to_string_lossy().strip_suffix(".rs") {
+            (stem.to_owned(), path.clone())
+        } else {
+            continue;
+        };
 
-fn day_modules_for_year(path: PathBuf) -> Vec<String> {
-    read_dir(path)
-        .unwrap()
-        .into_iter()
-        .filter_map(|entry| entry.map_or(None, some_if_day_module))
-        .collect()
+        let day = match parse_number(&name, "day", 1..=25) {
+            Ok(day) => day,
+            Err(problem) => {
+                problems.push(format!("{}: {problem}", path.display()));
+                continue;
+            }
+        };
+        if !module_path.is_file() {
+            problems.push(format!("{}: missing mod.rs", path.display()));
+        } else if let Some(existing) = days.insert(day, module_path) {
+            problems.push(format!(
+                "{}: day {day} is already defined by {}",
+                path.display(),
+                existing.display()
+            ));
+        }
+    }
+    days
 }
 
-fn some_if_day_module(entry: DirEntry) -> Option<String> {
-    let file_name = entry.file_name().to_str().unwrap().to_owned();
-    file_name
-        .starts_with("day")
-        .then(|| file_name.trim_end_matches(".rs").to_owned())
+/// Parse names like `year2023` or `day5`. Numbers must be in range and have no leading zeroes,
+/// so that each module name maps to exactly one number.
+fn parse_number<T>(
+    name: &str,
+    prefix: &str,
+    range: std::ops::RangeInclusive<T>,
+) -> Result<T, String>
+where
+    T: std::str::FromStr + PartialOrd + std::fmt::Display,
+{
+    let expected = format!("expected a name like {prefix}{}", range.start());
+    let Some(digits) = name.strip_prefix(prefix) else {
+        return Err(expected);
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(expected);
+    }
+    if digits.starts_with('0') {
+        let trimmed = digits.trim_start_matches('0');
+        return Err(format!("remove the leading zero, e.g. {prefix}{trimmed}"));
+    }
+    match digits.parse::<T>() {
+        Ok(number) if range.contains(&number) => Ok(number),
+        _ => Err(format!(
+            "{prefix} must be between {} and {}",
+            range.start(),
+            range.end()
+        )),
+    }
 }
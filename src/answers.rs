@@ -0,0 +1,167 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use toml::{Table, Value};
+
+use crate::runner::{Outcome, PartRun};
+
+/// Known answers, read from one TOML file per year such as `answers/2023.toml`:
+/// ```toml
+/// [day3]
+/// part1 = 4361
+/// part2 = "467835"
+/// ```
+#[derive(Debug, Default)]
+pub struct AnswerStore {
+    answers: HashMap<(u16, u8, u8), String>,
+}
+
+/// The result of comparing a computed answer with the expected one.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "fail (expected {expected})"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl AnswerStore {
+    /// The file holding the known answers for a year, e.g. `answers/2023.toml`.
+    pub fn path(year: u16) -> PathBuf {
+        PathBuf::from(format!("answers/{year}.toml"))
+    }
+
+    /// Load the answers for every given year. Years without an answers file have no known answers.
+    pub fn load(years: impl IntoIterator<Item = u16>) -> Result<Self> {
+        let mut store = Self::default();
+        for year in years.into_iter().collect::<BTreeSet<u16>>() {
+            let path = Self::path(year);
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                Err(error) => {
+                    return Err(error).with_context(|| format!("cannot read {}", path.display()))
+                }
+            };
+            store
+                .add_year(year, &content)
+                .with_context(|| format!("invalid answers file {}", path.display()))?;
+        }
+        Ok(store)
+    }
+
+    fn add_year(&mut self, year: u16, content: &str) -> Result<()> {
+        let table: Table = content.parse()?;
+        for (day_key, parts) in table {
+            let Some(day) = day_key.strip_prefix("day").and_then(|day| day.parse().ok()) else {
+                bail!("expected a table named like [day5], found [{day_key}]");
+            };
+            let Value::Table(parts) = parts else {
+                bail!("[{day_key}] should be a table of part answers");
+            };
+            for (part_key, answer) in parts {
+                let part = match part_key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => bail!("[{day_key}] has unknown key '{part_key}'"),
+                };
+                let answer = match answer {
+                    Value::String(text) => text,
+                    Value::Integer(number) => number.to_string(),
+                    other => {
+                        bail!("[{day_key}] {part_key} should be a string or integer, found {other}")
+                    }
+                };
+                self.answers.insert((year, day, part), answer);
+            }
+        }
+        Ok(())
+    }
+
+    /// Compare the outcome of a run with the known answer. A part that fails to produce an answer
+    /// when one is expected counts as a failure.
+    pub fn check(&self, run: &PartRun) -> Verdict {
+        let Some(expected) = self.answers.get(&(run.year, run.day, run.part)) else {
+            return Verdict::Unknown;
+        };
+        match &run.outcome {
+            Outcome::Solved(answer) if answer.to_string() == *expected => Verdict::Pass,
+            _ => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{AnswerStore, Verdict};
+    use crate::runner::{Outcome, PartRun};
+
+    fn run(day: u8, part: u8, outcome: Outcome) -> PartRun {
+        PartRun {
+            year: 2023,
+            day,
+            part,
+            outcome,
+            elapsed: Duration::ZERO,
+            verdict: None,
+        }
+    }
+
+    #[test]
+    fn checking_answers() {
+        let mut store = AnswerStore::default();
+        store
+            .add_year(2023, "[day3]\npart1 = 4361\npart2 = \"467835\"\n")
+            .unwrap();
+        assert_eq!(
+            store.check(&run(3, 1, Outcome::Solved(4361.into()))),
+            Verdict::Pass
+        );
+        assert_eq!(
+            store.check(&run(3, 2, Outcome::Solved(467_835.into()))),
+            Verdict::Pass
+        );
+        assert_eq!(
+            store.check(&run(3, 1, Outcome::Solved(4362.into()))),
+            Verdict::Fail {
+                expected: String::from("4361")
+            }
+        );
+        assert_eq!(
+            store.check(&run(3, 2, Outcome::Failed(String::from("oops")))),
+            Verdict::Fail {
+                expected: String::from("467835")
+            }
+        );
+        assert_eq!(
+            store.check(&run(4, 1, Outcome::Solved(13.into()))),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn rejecting_malformed_files() {
+        let mut store = AnswerStore::default();
+        assert!(store.add_year(2023, "[three]\npart1 = 1\n").is_err());
+        assert!(store.add_year(2023, "[day3]\npart3 = 1\n").is_err());
+        assert!(store.add_year(2023, "[day3]\npart1 = 1.5\n").is_err());
+        assert!(store.add_year(2023, "day3 = 1\n").is_err());
+    }
+}
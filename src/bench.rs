@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Write};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+use crate::runner::{self, Entry, Outcome};
+
+/// A timed step of solving a day: parsing its input, or one of its parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Part(part) => write!(f, "{part}"),
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text == "parse" {
+            Ok(Step::Parse)
+        } else {
+            text.parse().map(Step::Part)
+        }
+    }
+}
+
+/// Timing statistics over repeated runs of one step.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    /// Summarize the samples. Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let seconds: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        #[allow(clippy::cast_precision_loss)]
+        let count = seconds.len() as f64;
+        let mean = seconds.iter().sum::<f64>() / count;
+        let variance = seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / count;
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// The benchmark of one step of one day.
+pub struct Bench {
+    pub year: u16,
+    pub day: u8,
+    pub step: Step,
+    pub runs: u32,
+    /// The statistics, or the status of the step if it could not be run.
+    pub stats: Result<Stats, String>,
+}
+
+/// Parse the input `runs` times, then run each part `runs` times on the parsed input. The parts
+/// are skipped if the input cannot be parsed.
+pub fn bench_day(
+    entry: &Entry,
+    input: &str,
+    parts: impl Iterator<Item = u8>,
+    runs: u32,
+) -> Vec<Bench> {
+    let bench = |step, stats| Bench {
+        year: entry.year,
+        day: entry.day,
+        step,
+        runs,
+        stats,
+    };
+    let mut parsed = None;
+    let parse_stats = sample(runs, || {
+        let (result, elapsed) = runner::parse_input(entry, input);
+        parsed = Some(result.map_err(|error| format!("{error:#}"))?);
+        Ok(elapsed)
+    });
+    let mut benches = vec![bench(Step::Parse, parse_stats)];
+    if let Some(parsed) = parsed {
+        benches.extend(parts.map(|part| {
+            let stats = sample(runs, || {
+                let run = runner::run_part(entry, parsed.as_ref(), part);
+                match run.outcome {
+                    Outcome::Solved(_) => Ok(run.elapsed),
+                    Outcome::Unsolved => Err(String::from("unsolved")),
+                    Outcome::MissingInput(error) | Outcome::Failed(error) => Err(error),
+                }
+            });
+            bench(Step::Part(part), stats)
+        }));
+    }
+    benches
+}
+
+/// Time `run` up to `runs` times, stopping at the first run that fails.
+fn sample(runs: u32, mut run: impl FnMut() -> Result<Duration, String>) -> Result<Stats, String> {
+    let samples = (0..runs)
+        .map(|_| run())
+        .collect::<Result<Vec<Duration>, String>>()?;
+    Stats::from_samples(&samples).ok_or_else(|| String::from("no runs"))
+}
+
+/// Median timings of a previous benchmark, keyed by year, day and step.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline(HashMap<(u16, u8, Step), Duration>);
+
+impl Baseline {
+    /// Read a baseline file, where each line is `<year> <day> <step> <median nanoseconds>` and
+    /// the step is `parse` or a part number. Blank lines and lines starting with `#` are ignored.
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("cannot read baseline {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("invalid baseline {}", path.display()))
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let mut medians = HashMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [year, day, step, nanos] = fields[..] else {
+                bail!("line {}: expected 4 fields in '{line}'", index + 1);
+            };
+            let parse_error = || format!("line {}: invalid number in '{line}'", index + 1);
+            let key = (
+                year.parse().with_context(parse_error)?,
+                day.parse().with_context(parse_error)?,
+                step.parse().with_context(parse_error)?,
+            );
+            let nanos: u64 = nanos.parse().with_context(parse_error)?;
+            medians.insert(key, Duration::from_nanos(nanos));
+        }
+        Ok(Self(medians))
+    }
+
+    /// Write the medians of every successful benchmark to `path`.
+    pub fn write(path: &Path, benches: &[Bench]) -> Result<()> {
+        let mut content = String::from("# year day step median_ns\n");
+        for bench in benches {
+            if let Ok(stats) = &bench.stats {
+                let nanos = stats.median.as_nanos();
+                writeln!(
+                    content,
+                    "{} {} {} {nanos}",
+                    bench.year, bench.day, bench.step
+                )?;
+            }
+        }
+        fs::write(path, content)
+            .with_context(|| format!("cannot write baseline {}", path.display()))
+    }
+
+    fn median(&self, bench: &Bench) -> Option<Duration> {
+        self.0.get(&(bench.year, bench.day, bench.step)).copied()
+    }
+}
+
+/// Print the benchmark statistics, with a comparison column when a baseline is given.
+pub fn print_report(benches: &[Bench], baseline: Option<&Baseline>) {
+    let mut header = vec![
+        "Year", "Day", "Step", "Runs", "Min", "Median", "Mean", "Std dev",
+    ];
+    if baseline.is_some() {
+        header.extend(["Baseline", "Change"]);
+    }
+    let rows: Vec<Vec<String>> = benches
+        .iter()
+        .map(|bench| {
+            let mut row = vec![
+                bench.year.to_string(),
+                bench.day.to_string(),
+                bench.step.to_string(),
+                bench.runs.to_string(),
+            ];
+            match &bench.stats {
+                Ok(stats) => {
+                    row.extend(
+                        [stats.min, stats.median, stats.mean, stats.std_dev]
+                            .map(|duration| format!("{duration:.2?}")),
+                    );
+                    if let Some(baseline) = baseline {
+                        match baseline.median(bench) {
+                            Some(previous) => row.extend([
+                                format!("{previous:.2?}"),
+                                percent_change(previous, stats.median),
+                            ]),
+                            None => row.extend(["-", "-"].map(String::from)),
+                        }
+                    }
+                }
+                Err(status) => row.push(status.clone()),
+            }
+            row
+        })
+        .collect();
+    runner::print_table(&header, &rows);
+}
+
+fn percent_change(previous: Duration, current: Duration) -> String {
+    if previous.is_zero() {
+        return String::from("-");
+    }
+    let change = (current.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0;
+    format!("{change:+.1}%")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Baseline, Stats, Step};
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+        assert_eq!(
+            Stats::from_samples(&millis(&[9, 2, 5, 4, 4, 7, 5, 4])),
+            Some(Stats {
+                min: Duration::from_millis(2),
+                median: Duration::from_micros(4500),
+                mean: Duration::from_millis(5),
+                std_dev: Duration::from_millis(2),
+            })
+        );
+        let odd = Stats::from_samples(&millis(&[3, 1, 2])).unwrap();
+        assert_eq!(odd.median, Duration::from_millis(2));
+    }
+
+    #[test]
+    fn baseline_parsing() {
+        let baseline =
+            Baseline::parse("# year day step median_ns\n\n2023 6 1 1500\n2023 6 parse 80\n")
+                .unwrap();
+        assert_eq!(
+            baseline.0.get(&(2023, 6, Step::Part(1))),
+            Some(&Duration::from_nanos(1500))
+        );
+        assert_eq!(
+            baseline.0.get(&(2023, 6, Step::Parse)),
+            Some(&Duration::from_nanos(80))
+        );
+        assert!(Baseline::parse("2023 6 1").is_err());
+        assert!(Baseline::parse("2023 six 1 1500").is_err());
+        assert!(Baseline::parse("2023 6 lex 1500").is_err());
+    }
+
+    #[test]
+    fn percent_change() {
+        let previous = Duration::from_millis(200);
+        assert_eq!(
+            super::percent_change(previous, Duration::from_millis(150)),
+            "-25.0%"
+        );
+        assert_eq!(
+            super::percent_change(previous, Duration::from_millis(300)),
+            "+50.0%"
+        );
+        assert_eq!(super::percent_change(Duration::ZERO, previous), "-");
+    }
+}
@@ -1,16 +1,98 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{ArgAction, ArgGroup, Parser, ValueEnum};
+
+use crate::trace::DayFilter;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(group(ArgGroup::new("selection").required(true).args(["year", "all"])))]
 pub struct Cli {
-    #[clap(short)]
-    pub year: String,
+    /// The puzzle year to run. Without `--day`, every registered day of the year is run.
+    #[clap(short, long)]
+    pub year: Option<u16>,
+
+    #[clap(short, long, requires = "year")]
+    pub day: Option<u8>,
+
+    /// Run every registered solution in calendar order.
+    #[clap(long, conflicts_with = "year")]
+    pub all: bool,
+
+    /// Defaults to `inputs/<year>/day<DD>.txt`. Use `-` to read the input from stdin.
+    #[clap(short, long, requires = "day")]
+    pub input_file: Option<PathBuf>,
+
+    /// Use the given text as the puzzle input, e.g. to try out an example.
+    #[clap(long, requires = "day", conflicts_with = "input_file")]
+    pub input_text: Option<String>,
+
+    /// Which part of the puzzle to run.
+    #[clap(long, value_enum, default_value_t = Part::Both)]
+    pub part: Part,
+
+    /// How to print the results of the run.
+    #[clap(long, value_enum, default_value_t = Format::Text, conflicts_with = "bench")]
+    pub format: Format,
+
+    /// Check answers against `answers/<year>.toml`, exiting with an error if any do not match.
+    #[clap(long, conflicts_with = "bench")]
+    pub verify: bool,
+
+    /// Run each part N times and report timing statistics instead of answers.
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub bench: Option<u32>,
+
+    /// Compare benchmark results against a baseline written by `--save-baseline`.
+    #[clap(long, requires = "bench")]
+    pub baseline: Option<PathBuf>,
 
-    #[clap(short)]
-    pub day: String,
+    /// Write the benchmark results to a baseline file.
+    #[clap(long, requires = "bench")]
+    pub save_baseline: Option<PathBuf>,
+
+    /// Write diagnostic output from the solvers to stderr. Repeat for more detail, e.g. `-vv`.
+    #[clap(short, long, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Only write diagnostic output from these days, e.g. `2023/5` or a whole year like `2015`.
+    /// Implies `-v` unless more detail is asked for.
+    #[clap(long, value_name = "YEAR[/DAY]", value_delimiter = ',')]
+    pub trace_day: Vec<DayFilter>,
+}
+
+impl Cli {
+    /// Returns true if more than one day can be selected by the arguments.
+    pub fn is_batch(&self) -> bool {
+        self.day.is_none()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One line per part, with a summary table for batch runs.
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
 
-    #[clap(short)]
-    pub input_file: PathBuf,
+impl Part {
+    /// The part numbers to run, in order.
+    pub fn numbers(self) -> impl Iterator<Item = u8> {
+        let numbers: &[u8] = match self {
+            Part::One => &[1],
+            Part::Two => &[2],
+            Part::Both => &[1, 2],
+        };
+        numbers.iter().copied()
+    }
 }
@@ -0,0 +1,268 @@
+use std::fmt::{self, Display};
+
+use anyhow::{bail, Result};
+
+use crate::point::Point;
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// Cells are addressed by [`Point`]s, where `x` is the column and `y` is the row, counted from the
+/// top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /// Parse a grid with one cell per character, one row per line.
+    ///
+    /// # Errors
+    /// Returns an error if the lines are not all the same length.
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, Ok)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parse a grid with one line per row, converting each character into a cell.
+    ///
+    /// # Errors
+    /// Returns an error if the lines are not all the same length, or if a character cannot be
+    /// converted.
+    pub fn parse_with(input: &str, mut parse: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            let row_start = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                match parse(c) {
+                    Ok(cell) => cells.push(cell),
+                    Err(error) => bail!("line {}, column {}: {error:#}", index + 1, column + 1),
+                }
+            }
+            let row_width = cells.len() - row_start;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => bail!(
+                    "line {}: expected {width} columns like the first line, found {row_width}",
+                    index + 1
+                ),
+                Some(_) => (),
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// Create a grid from cells listed row by row.
+    ///
+    /// # Errors
+    /// Returns an error if the number of cells does not fill the grid exactly.
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Result<Self> {
+        if cells.len() != width * height {
+            bail!(
+                "a {width}x{height} grid needs {} cells, found {}",
+                width * height,
+                cells.len()
+            );
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        self.index(point).is_some()
+    }
+
+    /// Returns the cell at a point, or `None` if the point is outside of the grid.
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index(point).map(|index| &mut self.cells[index])
+    }
+
+    /// Iterate over every point and its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().enumerate().map(|(index, cell)| {
+            let point = Point::from_indices(index % self.width, index / self.width);
+            (point, cell)
+        })
+    }
+
+    /// Iterate over the rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero size, but an empty grid has no cells to chunk anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterate over the cells of a column from top to bottom.
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        let cells = if column < self.width {
+            &self.cells[column..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Iterate over the columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /// Iterate over the points and cells in the rectangle between two corners, inclusive, row by
+    /// row. The region is clipped to the grid.
+    pub fn region(
+        &self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        (top_left.y..=bottom_right.y).flat_map(move |y| {
+            (top_left.x..=bottom_right.x).filter_map(move |x| {
+                let point = Point::new(x, y);
+                self.get(point).map(|cell| (point, cell))
+            })
+        })
+    }
+
+    /// The points north, east, south and west of a point that are inside the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbours4()
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// The points around a point, including diagonals, that are inside the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbours8()
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::point::Point;
+
+    fn example() -> Grid<char> {
+        Grid::parse("abc\ndef").unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert!(Grid::parse("abc\nde").is_err());
+
+        let digits = Grid::parse_with("12\n34", |c| Ok(c.to_digit(10).unwrap())).unwrap();
+        assert_eq!(digits.get(Point::new(0, 1)), Some(&3));
+        let error = Grid::parse_with("12\n3x", |c| {
+            c.to_digit(10)
+                .ok_or_else(|| anyhow::anyhow!("'{c}' is not a digit"))
+        })
+        .unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: 'x' is not a digit");
+    }
+
+    #[test]
+    fn bounds_checked_access() {
+        let mut grid = example();
+        assert_eq!(grid.get(Point::new(2, 0)), Some(&'c'));
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        *grid.get_mut(Point::new(1, 1)).unwrap() = 'E';
+        assert_eq!(grid.to_string(), "abc\ndEf");
+    }
+
+    #[test]
+    fn iterating_points() {
+        let grid = example();
+        let (point, cell) = grid.iter().nth(4).unwrap();
+        assert_eq!((point, *cell), (Point::new(1, 1), 'e'));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = example();
+        let letters = |points: Vec<Point>| {
+            points
+                .into_iter()
+                .map(|point| grid.get(point).unwrap())
+                .collect::<String>()
+        };
+        assert_eq!(letters(grid.neighbours4(Point::ORIGIN).collect()), "bd");
+        assert_eq!(
+            letters(grid.neighbours8(Point::new(1, 1)).collect()),
+            "bcfda"
+        );
+    }
+
+    #[test]
+    fn rows_columns_and_regions() {
+        let grid = example();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(Iterator::collect::<String>)
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(
+            grid.region(Point::new(1, -1), Point::new(4, 1))
+                .map(|(_, c)| c)
+                .collect::<String>(),
+            "bcef"
+        );
+    }
+}
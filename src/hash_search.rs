@@ -0,0 +1,175 @@
+use md5::{Context, Digest};
+use rayon::prelude::*;
+
+/// How many indexes each batch of the parallel search hashes.
+const CHUNK_SIZE: u64 = 1 << 14;
+
+/// Search the MD5 hashes of a fixed prefix followed by increasing decimal indexes, like
+/// `abcdef0`, `abcdef1`, `abcdef2` and so on.
+///
+/// The hasher state for the prefix is computed once and cloned for each index. Puzzles like
+/// 2015 day 4 want the first match, while ones like 2016 days 5 and 14 read digits out of a
+/// stream of matches with [`HashSearch::matches`] and [`nibble`].
+#[derive(Clone)]
+pub struct HashSearch {
+    prefix: Context,
+}
+
+impl HashSearch {
+    #[must_use]
+    pub fn new(prefix: &str) -> Self {
+        let mut context = Context::new();
+        context.consume(prefix);
+        Self { prefix: context }
+    }
+
+    /// The hash of the prefix followed by `index`.
+    #[must_use]
+    pub fn digest(&self, index: u64) -> Digest {
+        let mut context = self.prefix.clone();
+        let mut buffer = [0; 20];
+        context.consume(decimal(index, &mut buffer));
+        context.compute()
+    }
+
+    /// Every index from `start` onwards whose hash matches, in ascending order, with its hash.
+    ///
+    /// Indexes are hashed in parallel a chunk at a time, so a few more hashes than needed may be
+    /// computed past the last match that is used.
+    pub fn matches<'a>(
+        &'a self,
+        start: u64,
+        matches: impl Fn(&Digest) -> bool + Sync + 'a,
+    ) -> impl Iterator<Item = (u64, Digest)> + 'a {
+        std::iter::successors(Some(start), |chunk_start| {
+            chunk_start.checked_add(CHUNK_SIZE)
+        })
+        .flat_map(move |chunk_start| {
+            let chunk = chunk_start..chunk_start.saturating_add(CHUNK_SIZE);
+            let found: Vec<(u64, Digest)> = chunk
+                .into_par_iter()
+                .filter_map(|index| {
+                    let digest = self.digest(index);
+                    matches(&digest).then_some((index, digest))
+                })
+                .collect();
+            found
+        })
+    }
+
+    /// The lowest index whose hash matches.
+    ///
+    /// # Panics
+    /// Panics if no index up to `u64::MAX` matches.
+    #[must_use]
+    pub fn first_match(&self, matches: impl Fn(&Digest) -> bool + Sync) -> u64 {
+        let (index, _) = self
+            .matches(0, matches)
+            .next()
+            .expect("some index should match before the indexes run out");
+        index
+    }
+}
+
+/// Count the zero hex digits at the start of a hash, without formatting it as text.
+#[must_use]
+pub fn leading_zero_nibbles(digest: &Digest) -> u32 {
+    let zero_bits = digest
+        .0
+        .iter()
+        .position(|&byte| byte != 0)
+        .map_or(128, |index| {
+            // `usize` to `u32` cannot truncate here, as a digest only has 16 bytes.
+            #[allow(clippy::cast_possible_truncation)]
+            let whole_bytes = index as u32;
+            whole_bytes * 8 + digest.0[index].leading_zeros()
+        });
+    zero_bits / 4
+}
+
+/// The hex digit at a position in a hash, counting from the left.
+///
+/// # Panics
+/// Panics if `position` is 32 or more.
+#[must_use]
+pub fn nibble(digest: &Digest, position: usize) -> u8 {
+    let byte = digest.0[position / 2];
+    if position.is_multiple_of(2) {
+        byte >> 4
+    } else {
+        byte & 0xf
+    }
+}
+
+/// Write a number's decimal digits into the end of a buffer and return them.
+fn decimal(mut number: u64, buffer: &mut [u8; 20]) -> &[u8] {
+    let mut start = buffer.len();
+    loop {
+        start -= 1;
+        // A single digit always fits in a byte.
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (number % 10) as u8;
+        buffer[start] = b'0' + digit;
+        number /= 10;
+        if number == 0 {
+            return &buffer[start..];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decimal, leading_zero_nibbles, nibble, HashSearch};
+
+    #[test]
+    fn decimal_digits() {
+        let mut buffer = [0; 20];
+        assert_eq!(decimal(0, &mut buffer), b"0");
+        assert_eq!(decimal(609_043, &mut buffer), b"609043");
+        assert_eq!(
+            decimal(u64::MAX, &mut buffer),
+            u64::MAX.to_string().as_bytes()
+        );
+    }
+
+    #[test]
+    fn digests_match_formatted_input() {
+        let search = HashSearch::new("abcdef");
+        for index in [0, 7, 609_043] {
+            assert_eq!(search.digest(index), md5::compute(format!("abcdef{index}")));
+        }
+    }
+
+    #[test]
+    fn counting_zero_nibbles() {
+        let digest = HashSearch::new("abcdef").digest(609_043);
+        assert!(format!("{digest:x}").starts_with("000001dbbfa"));
+        assert_eq!(leading_zero_nibbles(&digest), 5);
+        assert_eq!(nibble(&digest, 5), 1);
+        assert_eq!(nibble(&digest, 6), 0xd);
+        assert_eq!(leading_zero_nibbles(&md5::Digest([0; 16])), 32);
+        let mut digest = md5::Digest([0; 16]);
+        digest.0[1] = 0x0f;
+        assert_eq!(leading_zero_nibbles(&digest), 3);
+    }
+
+    #[test]
+    fn matches_come_in_order() {
+        let search = HashSearch::new("abcdef");
+        let three_zeroes = |digest: &md5::Digest| leading_zero_nibbles(digest) >= 3;
+        let expected: Vec<u64> = (0..50_000)
+            .filter(|&index| three_zeroes(&search.digest(index)))
+            .collect();
+        let found: Vec<u64> = search
+            .matches(0, three_zeroes)
+            .map(|(index, _)| index)
+            .take_while(|&index| index < 50_000)
+            .collect();
+        assert!(expected.len() > 3);
+        assert_eq!(found, expected);
+        assert_eq!(
+            search.matches(expected[1], three_zeroes).next().unwrap().0,
+            expected[1]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use std::ops::{Add, Range, Sub};
+
+/// A set of values stored as sorted, disjoint half-open ranges.
+///
+/// Ranges that overlap or touch are merged, and empty ranges are dropped, so two sets holding
+/// the same values always compare equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        // The first range ending after the value is the only one that can hold it.
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= value)
+    }
+
+    /// The smallest value in the set.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// Iterate over the ranges in ascending order.
+    pub fn ranges(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.union(&Self::from(range));
+    }
+
+    /// Every value in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges: Vec<Range<T>> = self.ranges().chain(other.ranges()).collect();
+        ranges.sort_unstable_by_key(|range| range.start);
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    /// Every value in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut left, mut right) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            // Whichever range ends first cannot overlap anything further along the other set.
+            if a.end <= b.end {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Every value in this set but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut removed = other.ranges.iter().peekable();
+        for range in &self.ranges {
+            let mut start = range.start;
+            while let Some(hole) = removed.peek() {
+                if hole.end <= start {
+                    removed.next();
+                    continue;
+                }
+                if hole.start >= range.end {
+                    break;
+                }
+                if hole.start > start {
+                    ranges.push(start..hole.start);
+                }
+                start = hole.end;
+                if hole.end >= range.end {
+                    break;
+                }
+                removed.next();
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Take the values inside `source` and move them so that `source.start` lands on
+    /// `destination`. Values outside of `source` are not included in the result.
+    #[must_use]
+    pub fn map_range(&self, source: Range<T>, destination: T) -> Self {
+        let moved = self
+            .intersection(&Self::from(source.clone()))
+            .ranges()
+            .map(|range| {
+                range.start - source.start + destination..range.end - source.start + destination
+            })
+            .collect::<Vec<_>>();
+        Self { ranges: moved }
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let ranges = if range.start < range.end {
+            vec![range]
+        } else {
+            Vec::new()
+        };
+        Self { ranges }
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let ranges = iter
+            .into_iter()
+            .filter(|range| range.start < range.end)
+            .collect();
+        // A union with an empty set sorts and merges the ranges.
+        Self::new().union(&Self { ranges })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use super::IntervalSet;
+
+    fn set(ranges: &[Range<u64>]) -> IntervalSet<u64> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn values(set: &IntervalSet<u64>) -> Vec<u64> {
+        set.ranges().flatten().collect()
+    }
+
+    #[test]
+    fn normalising() {
+        let merged = set(&[5..8, 1..3, 3..4, 7..10, 12..12]);
+        assert_eq!(merged.ranges().collect::<Vec<_>>(), vec![1..4, 5..10]);
+        assert_eq!(merged.min(), Some(1));
+        assert!(merged.contains(3));
+        assert!(!merged.contains(4));
+        assert!(merged.contains(9));
+        assert!(!merged.contains(10));
+        assert!(IntervalSet::from(4..4).is_empty());
+
+        let mut inserted = IntervalSet::new();
+        inserted.insert(10..12);
+        inserted.insert(0..2);
+        inserted.insert(2..10);
+        assert_eq!(inserted, IntervalSet::from(0..12));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..5, 10..15, 20..25]);
+        let b = set(&[3..12, 14..21, 30..31]);
+        assert_eq!(a.union(&b), set(&[0..25, 30..31]));
+        assert_eq!(a.intersection(&b), set(&[3..5, 10..12, 14..15, 20..21]));
+        assert_eq!(a.difference(&b), set(&[0..3, 12..14, 21..25]));
+        assert_eq!(b.difference(&a), set(&[5..10, 15..20, 30..31]));
+        assert_eq!(a.difference(&IntervalSet::from(0..30)), IntervalSet::new());
+        assert_eq!(
+            a.difference(&set(&[1..2, 3..4])),
+            set(&[0..1, 2..3, 4..5, 10..15, 20..25])
+        );
+    }
+
+    #[test]
+    fn operations_match_individual_values() {
+        let sets = [
+            set(&[]),
+            IntervalSet::from(0..4),
+            set(&[2..3, 5..9]),
+            set(&[1..2, 3..4, 6..7, 8..10]),
+            IntervalSet::from(0..10),
+        ];
+        for a in &sets {
+            for b in &sets {
+                let (a_values, b_values) = (values(a), values(b));
+                let union: Vec<u64> = (0..10)
+                    .filter(|v| a_values.contains(v) || b_values.contains(v))
+                    .collect();
+                let intersection: Vec<u64> = a_values
+                    .iter()
+                    .copied()
+                    .filter(|v| b_values.contains(v))
+                    .collect();
+                let difference: Vec<u64> = a_values
+                    .iter()
+                    .copied()
+                    .filter(|v| !b_values.contains(v))
+                    .collect();
+                assert_eq!(values(&a.union(b)), union, "{a:?} | {b:?}");
+                assert_eq!(values(&a.intersection(b)), intersection, "{a:?} & {b:?}");
+                assert_eq!(values(&a.difference(b)), difference, "{a:?} - {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn mapping_ranges() {
+        let seeds = set(&[79..93, 55..68]);
+        assert_eq!(seeds.map_range(50..98, 52), set(&[57..70, 81..95]));
+        assert_eq!(seeds.map_range(60..80, 0), set(&[0..8, 19..20]));
+        assert!(seeds.map_range(0..10, 100).is_empty());
+    }
+}
@@ -1,27 +1,123 @@
 #![warn(clippy::pedantic)]
 
+mod answers;
+mod bench;
 mod cli;
-mod io;
+pub mod grid;
+pub mod hash_search;
+pub mod interval;
+pub mod matcher;
+pub mod math;
+pub mod parse;
+pub mod point;
+mod report;
+mod runner;
 pub mod solver;
-pub mod year2015;
-pub mod year2023;
+pub mod trace;
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::process::ExitCode;
 
+use answers::{AnswerStore, Verdict};
+use bench::Baseline;
 use clap::Parser;
-use cli::Cli;
+use cli::{Cli, Format};
+use runner::{Entry, InputSource, Outcome, Registry};
+use solver::Registration;
 
 include!(concat!(env!("OUT_DIR"), "/module_selection.rs"));
 
-fn main() {
+fn main() -> anyhow::Result<ExitCode> {
     let args = Cli::parse();
+    trace::init(args.verbose, args.trace_day.clone());
 
-    let solution_selector: HashMap<String, Box<dyn Fn(PathBuf)>> = solution_selector();
-    let solution_lookup = format!("year{}::day{}", args.year, args.day);
+    let registry = Registry::new(SOLUTIONS);
+    let entries = registry.select(args.year, args.day);
+    if entries.is_empty() {
+        match (args.year, args.day) {
+            (Some(year), Some(day)) => {
+                println!("There is no solution written for {year} day {day} yet.");
+            }
+            (Some(year), None) => println!("There are no solutions written for {year} yet."),
+            _ => println!("There are no solutions written yet."),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(runs) = args.bench {
+        bench(&args, &entries, runs)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let answer_store = if args.verify {
+        Some(AnswerStore::load(entries.iter().map(|entry| entry.year))?)
+    } else {
+        None
+    };
+    let mut day_runs = Vec::new();
+    for entry in entries {
+        let mut day_run = runner::run(entry, &input_source(&args, entry), args.part);
+        for run in &mut day_run.parts {
+            run.verdict = answer_store.as_ref().map(|store| store.check(run));
+            if args.format == Format::Text {
+                println!("{}", run.describe());
+            }
+        }
+        if args.format == Format::Text && !args.is_batch() {
+            println!(
+                "Read the input in {:.2?} and parsed it in {:.2?}",
+                day_run.input_elapsed, day_run.parse_elapsed
+            );
+        }
+        day_runs.push(day_run);
+    }
+    match args.format {
+        Format::Text if args.is_batch() => runner::print_summary(&day_runs),
+        Format::Text => (),
+        Format::Json => report::print_json(&day_runs)?,
+        Format::Csv => report::print_csv(&day_runs),
+    }
+
+    let mut part_runs = day_runs.iter().flat_map(|day_run| &day_run.parts);
+    let any_errors = part_runs.clone().any(|run| match run.outcome {
+        Outcome::Failed(_) => true,
+        // Batch runs skip days whose inputs have not been downloaded.
+        Outcome::MissingInput(_) => !args.is_batch(),
+        Outcome::Solved(_) | Outcome::Unsolved => false,
+    });
+    let any_mismatches = part_runs.any(|run| matches!(run.verdict, Some(Verdict::Fail { .. })));
+    if any_mismatches {
+        eprintln!("Some answers do not match the answers store.");
+    }
+    if any_errors || any_mismatches {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn bench(args: &Cli, entries: &[&Entry], runs: u32) -> anyhow::Result<()> {
+    let baseline = args.baseline.as_deref().map(Baseline::read).transpose()?;
+    let mut benches = Vec::new();
+    for entry in entries {
+        match runner::load_input(&input_source(args, entry)) {
+            Ok((input, _)) => {
+                benches.extend(bench::bench_day(entry, &input, args.part.numbers(), runs));
+            }
+            Err(message) => println!("{} day {}: {message}", entry.year, entry.day),
+        }
+    }
+    bench::print_report(&benches, baseline.as_ref());
+    if let Some(path) = &args.save_baseline {
+        Baseline::write(path, &benches)?;
+    }
+    Ok(())
+}
 
-    match solution_selector.get(&solution_lookup) {
-        Some(solver) => solver(args.input_file),
-        None => println!("There is no solution written for {solution_lookup} yet."),
+fn input_source(args: &Cli, entry: &Entry) -> InputSource {
+    if let Some(text) = &args.input_text {
+        InputSource::Text(text.clone())
+    } else if let Some(path) = &args.input_file {
+        InputSource::from_path(path)
+    } else {
+        InputSource::default_for(entry.year, entry.day)
     }
 }
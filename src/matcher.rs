@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+
+/// Find many patterns in one pass over some text, using the Aho-Corasick algorithm.
+///
+/// Every occurrence of every pattern is found, including overlapping ones, so `eightwo` holds
+/// both `eight` and `two`. Each pattern carries a value that is returned with its matches.
+pub struct PatternMatcher<V> {
+    /// The next state for each state and input byte.
+    transitions: Vec<[usize; 256]>,
+    /// The patterns that end at each state, including those that are suffixes of others.
+    outputs: Vec<Vec<usize>>,
+    /// The length and value of each pattern.
+    patterns: Vec<(usize, V)>,
+    longest: usize,
+}
+
+/// A pattern found in some text, covering the bytes `start..end`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Match<'a, V> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'a V,
+}
+
+impl<V> PatternMatcher<V> {
+    /// Build a matcher from patterns and the value each one stands for.
+    ///
+    /// # Panics
+    /// Panics if a pattern is empty.
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = (P, V)>) -> Self {
+        // Start with a trie of the patterns, where state 0 is the root.
+        let mut trie: Vec<[Option<usize>; 256]> = vec![[None; 256]];
+        let mut outputs = vec![Vec::new()];
+        let mut lengths_and_values = Vec::new();
+        for (index, (pattern, value)) in patterns.into_iter().enumerate() {
+            let bytes = pattern.as_ref();
+            assert!(!bytes.is_empty(), "patterns cannot be empty");
+            let mut state = 0;
+            for &byte in bytes {
+                state = if let Some(next) = trie[state][usize::from(byte)] {
+                    next
+                } else {
+                    trie.push([None; 256]);
+                    outputs.push(Vec::new());
+                    let next = trie.len() - 1;
+                    trie[state][usize::from(byte)] = Some(next);
+                    next
+                };
+            }
+            outputs[state].push(index);
+            lengths_and_values.push((bytes.len(), value));
+        }
+
+        // Walk the trie breadth first, so that the state a mismatch falls back to is always
+        // finished before the states that fall back to it.
+        let mut transitions = vec![[0; 256]; trie.len()];
+        let mut fallbacks = vec![0; trie.len()];
+        let mut queue = VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            if state != 0 {
+                let inherited = outputs[fallbacks[state]].clone();
+                outputs[state].extend(inherited);
+            }
+            for byte in 0..256 {
+                match trie[state][byte] {
+                    Some(child) => {
+                        fallbacks[child] = if state == 0 {
+                            0
+                        } else {
+                            transitions[fallbacks[state]][byte]
+                        };
+                        transitions[state][byte] = child;
+                        queue.push_back(child);
+                    }
+                    None if state != 0 => {
+                        transitions[state][byte] = transitions[fallbacks[state]][byte];
+                    }
+                    None => (),
+                }
+            }
+        }
+
+        let longest = lengths_and_values
+            .iter()
+            .map(|(length, _)| *length)
+            .max()
+            .unwrap_or(0);
+        Self {
+            transitions,
+            outputs,
+            patterns: lengths_and_values,
+            longest,
+        }
+    }
+
+    /// Every match in the text, in order of where they end.
+    pub fn find_iter<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Match<'a, V>> + 'a {
+        text.bytes()
+            .scan(0, move |state, byte| {
+                *state = self.transitions[*state][usize::from(byte)];
+                Some(*state)
+            })
+            .enumerate()
+            .flat_map(move |(index, state)| {
+                self.outputs[state].iter().map(move |&pattern| {
+                    let (length, value) = &self.patterns[pattern];
+                    Match {
+                        start: index + 1 - length,
+                        end: index + 1,
+                        value,
+                    }
+                })
+            })
+    }
+
+    /// The match that starts first. Scanning stops as soon as no later match could start sooner.
+    #[must_use]
+    pub fn first<'a>(&'a self, text: &'a str) -> Option<Match<'a, V>> {
+        let mut first: Option<Match<V>> = None;
+        for found in self.find_iter(text) {
+            if let Some(best) = &first {
+                if found.end >= best.start + self.longest {
+                    break;
+                }
+                if found.start >= best.start {
+                    continue;
+                }
+            }
+            first = Some(found);
+        }
+        first
+    }
+
+    /// The match that starts last.
+    #[must_use]
+    pub fn last<'a>(&'a self, text: &'a str) -> Option<Match<'a, V>> {
+        self.find_iter(text).max_by_key(|found| found.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PatternMatcher;
+
+    #[test]
+    fn overlapping_matches() {
+        let matcher = PatternMatcher::new([("he", 1), ("she", 2), ("his", 3), ("hers", 4)]);
+        let found: Vec<(usize, usize, i32)> = matcher
+            .find_iter("ushers")
+            .map(|found| (found.start, found.end, *found.value))
+            .collect();
+        assert_eq!(found, vec![(1, 4, 2), (2, 4, 1), (2, 6, 4)]);
+        assert_eq!(matcher.find_iter("ahishers").count(), 4);
+        assert_eq!(matcher.find_iter("xyz").count(), 0);
+    }
+
+    #[test]
+    fn first_and_last() {
+        let matcher = PatternMatcher::new([("eight", 8), ("two", 2), ("one", 1)]);
+        assert_eq!(matcher.first("xeightwo").map(|found| *found.value), Some(8));
+        assert_eq!(matcher.last("xeightwo").map(|found| *found.value), Some(2));
+        assert_eq!(matcher.last("twone").map(|found| found.start), Some(2));
+        assert!(matcher.first("nothing here").is_none());
+        assert!(matcher.last("").is_none());
+    }
+
+    #[test]
+    fn first_match_can_end_after_a_shorter_one() {
+        // `bc` is found before `abcd`, but `abcd` starts first.
+        let matcher = PatternMatcher::new([("abcd", 'l'), ("bc", 's')]);
+        let first = matcher.first("zabcd").unwrap();
+        assert_eq!((first.start, *first.value), (1, 'l'));
+    }
+}
@@ -0,0 +1,240 @@
+use std::ops::RangeInclusive;
+
+/// The integers `x` where `x * (sum - x) > product`.
+///
+/// These are the integers strictly between the roots of `x² - sum·x + product`. The roots are
+/// found with an integer square root and then nudged onto the exact boundary, so there is no
+/// floating point rounding, and a root that is itself an integer is correctly left out.
+///
+/// Returns `None` if there are no such integers.
+#[must_use]
+pub fn between_roots(sum: u64, product: u64) -> Option<RangeInclusive<u64>> {
+    let (wide_sum, product) = (u128::from(sum), u128::from(product));
+    let beats = |x: u128| x * (wide_sum - x) > product;
+
+    let discriminant = (wide_sum * wide_sum).checked_sub(4 * product)?;
+    // Rounding in `isqrt` and the division leaves this within one of the lower root.
+    let mut low = (wide_sum - discriminant.isqrt()) / 2;
+    while low > 0 && beats(low - 1) {
+        low -= 1;
+    }
+    while low <= wide_sum / 2 && !beats(low) {
+        low += 1;
+    }
+    if low > wide_sum / 2 {
+        return None;
+    }
+    // The parabola is symmetric, so the upper bound mirrors the lower one.
+    let low = u64::try_from(low).ok()?;
+    Some(low..=sum - low)
+}
+
+/// The greatest common divisor, where `gcd(0, 0)` is 0.
+#[must_use]
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, where the multiple of anything and 0 is 0.
+///
+/// # Panics
+/// Panics if the multiple does not fit in a `u64`.
+#[must_use]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b))
+        .checked_mul(b)
+        .expect("the least common multiple should fit in u64")
+}
+
+/// Find the smallest `x` where `x % modulus == remainder` for every `(remainder, modulus)` pair,
+/// using the Chinese remainder theorem.
+///
+/// The moduli do not need to be coprime. Returns `x` together with the least common multiple of
+/// the moduli, since adding that to `x` gives every other solution. Returns `None` if the
+/// congruences contradict each other, a modulus is 0, or the numbers do not fit in a `u64`.
+#[must_use]
+pub fn chinese_remainder(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    let (mut solution, mut combined) = (0i128, 1i128);
+    for (remainder, modulus) in congruences {
+        if modulus == 0 {
+            return None;
+        }
+        let (remainder, modulus) = (i128::from(remainder), i128::from(modulus));
+        // Solve solution + combined * k = remainder (mod modulus) for k.
+        let (divisor, inverse, _) = extended_gcd(combined, modulus);
+        let difference = remainder - solution;
+        if difference % divisor != 0 {
+            return None;
+        }
+        let step = modulus / divisor;
+        let next_combined = combined
+            .checked_mul(step)
+            .filter(|next| *next <= i128::from(u64::MAX))?;
+        // Both factors are below `step`, so their product fits in a `u128`.
+        let factor = u128::try_from((difference / divisor).rem_euclid(step)).ok()?;
+        let inverse = u128::try_from(inverse.rem_euclid(step)).ok()?;
+        let k = i128::try_from(factor * inverse % u128::try_from(step).ok()?).ok()?;
+        // The solution stays below the combined modulus, since k is below `step`.
+        solution += combined * k;
+        combined = next_combined;
+    }
+    Some((u64::try_from(solution).ok()?, u64::try_from(combined).ok()?))
+}
+
+/// Find the greatest common divisor of `a` and `b`, along with coefficients `x` and `y` where
+/// `a·x + b·y` is that divisor.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (divisor, x, y) = extended_gcd(b, a % b);
+        (divisor, y, x - a / b * y)
+    }
+}
+
+/// Predict the value after a sequence, assuming that repeatedly taking the differences between
+/// neighbouring values eventually gives all zeroes, as it does for any polynomial.
+///
+/// Returns `None` for an empty sequence.
+#[must_use]
+pub fn extrapolate_next(values: &[i64]) -> Option<i64> {
+    let mut next = *values.last()?;
+    let mut differences: Vec<i64> = values.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    while differences.iter().any(|difference| *difference != 0) {
+        next += differences[differences.len() - 1];
+        differences = differences
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect();
+    }
+    Some(next)
+}
+
+/// Predict the value before a sequence, like [`extrapolate_next`] does for the value after it.
+#[must_use]
+pub fn extrapolate_previous(values: &[i64]) -> Option<i64> {
+    let reversed: Vec<i64> = values.iter().rev().copied().collect();
+    extrapolate_next(&reversed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        between_roots, chinese_remainder, extrapolate_next, extrapolate_previous, gcd, lcm,
+    };
+
+    fn brute_force(sum: u64, product: u64) -> Vec<u64> {
+        (0..=sum).filter(|x| x * (sum - x) > product).collect()
+    }
+
+    #[test]
+    fn race_examples() {
+        assert_eq!(between_roots(7, 9), Some(2..=5));
+        assert_eq!(between_roots(15, 40), Some(4..=11));
+        // 10 * 20 is exactly 200, so 10 and 20 do not count.
+        assert_eq!(between_roots(30, 200), Some(11..=19));
+        assert_eq!(between_roots(71_530, 940_200), Some(14..=71_516));
+    }
+
+    #[test]
+    fn no_solutions() {
+        assert_eq!(between_roots(0, 0), None);
+        assert_eq!(between_roots(4, 4), None);
+        assert_eq!(between_roots(4, 5), None);
+        assert_eq!(between_roots(3, 2), None);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for sum in 0..120 {
+            for product in 0..=sum * sum / 4 + 2 {
+                let expected = brute_force(sum, product);
+                let actual: Vec<u64> = between_roots(sum, product).into_iter().flatten().collect();
+                assert_eq!(actual, expected, "sum {sum}, product {product}");
+            }
+        }
+    }
+
+    #[test]
+    fn large_values() {
+        let sum = u64::MAX;
+        let range = between_roots(sum, 0).unwrap();
+        assert_eq!(range, 1..=sum - 1);
+        let perfect = 1_000_000_000u64;
+        assert_eq!(between_roots(2 * perfect, perfect * perfect), None);
+        assert_eq!(
+            between_roots(2 * perfect, perfect * perfect - 1),
+            Some(perfect..=perfect)
+        );
+    }
+
+    #[test]
+    fn divisors_and_multiples() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(gcd(0, 9), 9);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(21_251, 16_409), 21_251 * 16_409 / gcd(21_251, 16_409));
+        assert_eq!(lcm(0, 6), 0);
+    }
+
+    #[test]
+    fn remainders() {
+        assert_eq!(chinese_remainder([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // 4 and 6 share a factor, so the solution repeats every 12 rather than 24.
+        assert_eq!(chinese_remainder([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(chinese_remainder([(1, 2), (0, 4)]), None);
+        assert_eq!(chinese_remainder([(0, 0)]), None);
+        assert_eq!(chinese_remainder([]), Some((0, 1)));
+        // Remainders at least as large as the modulus are reduced.
+        assert_eq!(chinese_remainder([(7, 5)]), Some((2, 5)));
+        let big = u64::MAX - 1;
+        assert_eq!(
+            chinese_remainder([(big - 1, big), (1, 1)]),
+            Some((big - 1, big))
+        );
+        assert_eq!(chinese_remainder([(1, big), (1, big - 2)]), None);
+    }
+
+    #[test]
+    fn remainders_match_brute_force() {
+        for a in 1..12 {
+            for b in 1..12 {
+                for ra in 0..a {
+                    for rb in 0..b {
+                        let period = lcm(a, b);
+                        let expected = (0..period)
+                            .find(|x| x % a == ra && x % b == rb)
+                            .map(|x| (x, period));
+                        assert_eq!(chinese_remainder([(ra, a), (rb, b)]), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn extrapolating_sequences() {
+        assert_eq!(extrapolate_next(&[0, 3, 6, 9, 12, 15]), Some(18));
+        assert_eq!(extrapolate_previous(&[10, 13, 16, 21, 30, 45]), Some(5));
+        assert_eq!(extrapolate_next(&[7]), Some(7));
+        assert_eq!(extrapolate_previous(&[-4, -4]), Some(-4));
+        assert_eq!(extrapolate_next(&[]), None);
+        assert_eq!(extrapolate_previous(&[]), None);
+    }
+
+    #[test]
+    fn extrapolating_polynomials() {
+        let polynomial = |x: i64| 3 * x * x * x - 7 * x * x - 20 * x + 4;
+        let values: Vec<i64> = (0..6).map(polynomial).collect();
+        assert_eq!(extrapolate_next(&values), Some(polynomial(6)));
+        assert_eq!(extrapolate_previous(&values), Some(polynomial(-1)));
+    }
+}
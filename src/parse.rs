@@ -0,0 +1,227 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, one_of, space0, space1},
+    combinator::{map_res, opt, recognize},
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded, tuple},
+    IResult,
+};
+
+/// Parse each line of the input, adding the line number and text to any error.
+///
+/// # Errors
+/// Returns the first error from `parse`.
+pub fn lines<T>(input: &str, parse: impl Fn(&str) -> Result<T>) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).with_context(|| format!("line {}: '{line}'", index + 1)))
+        .collect()
+}
+
+/// Run a `nom` parser over the whole input. Trailing whitespace, like the final line break of
+/// a file, is allowed, but any other unconsumed input is an error.
+///
+/// # Errors
+/// Returns an error naming where parsing stopped if the parser fails or leaves input behind.
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<T> {
+    let (remaining, value) = parser(input).map_err(|error| nom_error(input, error))?;
+    let leftover = remaining.trim_start();
+    if !leftover.is_empty() {
+        let found = leftover.lines().next().unwrap_or_default();
+        bail!("{}: unexpected '{found}'", locate(input, leftover));
+    }
+    Ok(value)
+}
+
+/// Parse a number made of digits only, like `42`.
+///
+/// # Errors
+/// Fails if there are no digits or the number does not fit in `T`.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Parse a number with an optional sign, like `-7`, `+3` or `12`.
+///
+/// # Errors
+/// Fails if there are no digits or the number does not fit in `T`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Parse numbers separated by spaces on one line, like `41 48  83`.
+///
+/// # Errors
+/// Fails if the input does not start with a number.
+pub fn unsigned_list<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, unsigned)(input)
+}
+
+/// Parse signed numbers separated by spaces on one line, like `0 -3 6`.
+///
+/// # Errors
+/// Fails if the input does not start with a number.
+pub fn signed_list<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, signed)(input)
+}
+
+/// Parse a value after a label and a colon, like the numbers in `Time:  7  15  30`.
+pub fn labelled<'a, T>(
+    label: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, T> {
+    preceded(tuple((tag(label), char(':'), space0)), parser)
+}
+
+/// Parse a numbered header like `Card 12:`, returning the number. Spaces after the `:` are
+/// consumed too.
+pub fn header<'a, T: FromStr>(label: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, T> {
+    delimited(pair(tag(label), space1), unsigned, pair(char(':'), space0))
+}
+
+/// Parse blocks of lines separated by blank lines.
+pub fn blocks<'a, T>(
+    parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(blank_line, parser)
+}
+
+/// Parse the end of a line followed by an empty line.
+///
+/// # Errors
+/// Fails if the input does not start with two line endings.
+pub fn blank_line(input: &str) -> IResult<&str, &str> {
+    recognize(pair(line_ending, line_ending))(input)
+}
+
+/// Convert an error from a `nom` parser into one naming where parsing stopped and the text found
+/// there. The line is only named if the input has more than one line.
+#[must_use]
+pub fn nom_error(input: &str, error: nom::Err<nom::error::Error<&str>>) -> anyhow::Error {
+    match error {
+        nom::Err::Error(error) | nom::Err::Failure(error) => {
+            let found = error.input.lines().next().unwrap_or_default();
+            anyhow!(
+                "{}: expected {:?}, found '{found}'",
+                locate(input, error.input),
+                error.code
+            )
+        }
+        nom::Err::Incomplete(_) => anyhow!("the input ended unexpectedly"),
+    }
+}
+
+/// Describe where `remaining`, a suffix of `input`, starts, like `line 2, column 5`.
+#[must_use]
+pub fn locate(input: &str, remaining: &str) -> String {
+    let offset = input.len().saturating_sub(remaining.len());
+    let consumed = &input[..offset];
+    let line_start = consumed.rfind('\n').map_or(0, |index| index + 1);
+    let column = consumed[line_start..].chars().count() + 1;
+    if input.trim_end().contains('\n') {
+        let line = consumed.matches('\n').count() + 1;
+        format!("line {line}, column {column}")
+    } else {
+        format!("column {column}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::bail;
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{alpha1, line_ending},
+        multi::separated_list1,
+        sequence::separated_pair,
+        IResult,
+    };
+
+    use super::{blank_line, blocks, header, labelled, parse_all, signed_list, unsigned_list};
+
+    #[test]
+    fn line_errors() {
+        let parsed = super::lines("1\n2\n3", |line| Ok(line.parse::<i32>()?));
+        assert_eq!(parsed.unwrap(), vec![1, 2, 3]);
+
+        let error = super::lines("1\ntwo\n3", |line| Ok(line.parse::<i32>()?)).unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "line 2: 'two': invalid digit found in string"
+        );
+
+        let error = super::lines("", |_| -> anyhow::Result<()> { bail!("unused") });
+        assert!(error.unwrap().is_empty());
+    }
+
+    #[test]
+    fn nom_errors() {
+        fn distance(input: &str) -> IResult<&str, &str> {
+            tag("Distance:")(input)
+        }
+        let input = "Time: 7\nDistanse: 9";
+        let error = distance(&input[8..]).unwrap_err();
+        assert_eq!(
+            super::nom_error(input, error).to_string(),
+            "line 2, column 1: expected Tag, found 'Distanse: 9'"
+        );
+        let error = distance("Distance 9").unwrap_err();
+        assert_eq!(
+            super::nom_error("Distance 9", error).to_string(),
+            "column 1: expected Tag, found 'Distance 9'"
+        );
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            parse_all("3  15 0", unsigned_list::<u8>).unwrap(),
+            [3, 15, 0]
+        );
+        assert_eq!(
+            parse_all("0 -3 +6", signed_list::<i64>).unwrap(),
+            [0, -3, 6]
+        );
+        assert!(parse_all("-3", unsigned_list::<u8>).is_err());
+        assert!(parse_all("256", unsigned_list::<u8>).is_err());
+    }
+
+    #[test]
+    fn labels_and_headers() {
+        let mut time = labelled("Time", unsigned_list::<u64>);
+        assert_eq!(time("Time:      7  15   30"), Ok(("", vec![7, 15, 30])));
+        assert_eq!(header::<u32>("Card")("Card  12:  41 48"), Ok(("41 48", 12)));
+        assert!(header::<u32>("Card")("Game 1: 3").is_err());
+    }
+
+    #[test]
+    fn blank_line_blocks() {
+        let input = "a:\n1 2\n3\n\nb:\n4\n";
+        let block = separated_pair(
+            alpha1,
+            tag(":\n"),
+            separated_list1(line_ending, unsigned_list::<u32>),
+        );
+        let parsed = parse_all(input, blocks(block)).unwrap();
+        assert_eq!(
+            parsed,
+            vec![("a", vec![vec![1, 2], vec![3]]), ("b", vec![vec![4]])]
+        );
+        assert!(blank_line("\n1").is_err());
+    }
+
+    #[test]
+    fn unconsumed_input() {
+        let error = parse_all("1 2\n3 x", unsigned_list::<u32>).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: unexpected '3 x'");
+        let error = parse_all("1 2 x", unsigned_list::<u32>).unwrap_err();
+        assert_eq!(error.to_string(), "column 5: unexpected 'x'");
+    }
+}
@@ -0,0 +1,253 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use anyhow::{bail, Result};
+
+/// A position or offset on a 2D plane.
+///
+/// `x` grows to the right (east) and `y` grows downwards (south), like the rows of puzzle input.
+/// On a [`Grid`](crate::grid::Grid), `x` is the column and `y` is the row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The point at a column and row index, such as a cell of a [`Grid`](crate::grid::Grid).
+    ///
+    /// # Panics
+    /// Panics if an index does not fit in an `i64`.
+    #[must_use]
+    pub fn from_indices(column: usize, row: usize) -> Self {
+        let coordinate = |index| i64::try_from(index).expect("index should fit in i64");
+        Self::new(coordinate(column), coordinate(row))
+    }
+
+    /// The point one step away in the given direction.
+    #[must_use]
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// The distance when only moving horizontally or vertically.
+    #[must_use]
+    pub fn manhattan_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The distance when diagonal moves are allowed, like a king on a chess board.
+    #[must_use]
+    pub fn chebyshev_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The points one step away in each of the four cardinal directions.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::CARDINALS.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The points one step away in each of the eight directions.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// One of the eight compass directions, listed clockwise from north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+/// A change of heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    /// A quarter turn anticlockwise.
+    Left,
+    /// A quarter turn clockwise.
+    Right,
+    Around,
+}
+
+impl Direction {
+    pub const CARDINALS: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The offset of one step in this direction.
+    #[must_use]
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::NorthEast => Point::new(1, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::SouthEast => Point::new(1, 1),
+            Direction::South => Point::new(0, 1),
+            Direction::SouthWest => Point::new(-1, 1),
+            Direction::West => Point::new(-1, 0),
+            Direction::NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    #[must_use]
+    pub fn turn(self, turn: Turn) -> Direction {
+        let eighths = match turn {
+            Turn::Left => 6,
+            Turn::Right => 2,
+            Turn::Around => 4,
+        };
+        Direction::ALL[(self as usize + eighths) % Direction::ALL.len()]
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Direction {
+        self.turn(Turn::Around)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    /// Parse arrows like `^`, `>`, `v` and `<`, or the letters `N`, `E`, `S` and `W`.
+    fn try_from(c: char) -> Result<Self> {
+        Ok(match c {
+            '^' | 'N' | 'U' => Direction::North,
+            '>' | 'E' | 'R' => Direction::East,
+            'v' | 'S' | 'D' => Direction::South,
+            '<' | 'W' | 'L' => Direction::West,
+            _ => bail!("'{c}' is not a direction"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Point, Turn};
+
+    #[test]
+    fn arithmetic() {
+        let mut point = Point::new(2, 3);
+        assert_eq!(point + Point::new(1, -1), Point::new(3, 2));
+        assert_eq!(point - Point::new(1, -1), Point::new(1, 4));
+        assert_eq!(point * 3, Point::new(6, 9));
+        assert_eq!(-point, Point::new(-2, -3));
+        point += Direction::North.offset();
+        assert_eq!(point, Point::new(2, 2));
+        assert_eq!(point.step(Direction::SouthWest), Point::new(1, 3));
+        assert_eq!(Point::from_indices(4, 7), Point::new(4, 7));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 6);
+        let b = Point::new(5, 11);
+        assert_eq!(a.manhattan_distance(b), 9);
+        assert_eq!(a.chebyshev_distance(b), 5);
+        assert_eq!(b.manhattan_distance(a), 9);
+        assert_eq!(Point::ORIGIN.manhattan_distance(Point::new(-3, 4)), 7);
+    }
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::North.turn(Turn::Right), Direction::East);
+        assert_eq!(Direction::North.turn(Turn::Left), Direction::West);
+        assert_eq!(Direction::West.turn(Turn::Right), Direction::North);
+        assert_eq!(Direction::NorthEast.turn(Turn::Left), Direction::NorthWest);
+        assert_eq!(Direction::SouthEast.opposite(), Direction::NorthWest);
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.offset() + direction.opposite().offset(),
+                Point::ORIGIN
+            );
+        }
+    }
+
+    #[test]
+    fn parsing_arrows() {
+        let path: Vec<Direction> = "^>v<"
+            .chars()
+            .map(|c| Direction::try_from(c).unwrap())
+            .collect();
+        assert_eq!(path, Direction::CARDINALS);
+        assert!(Direction::try_from('x').is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        assert_eq!(Point::ORIGIN.neighbours4().count(), 4);
+        assert!(Point::ORIGIN
+            .neighbours8()
+            .all(|point| point.chebyshev_distance(Point::ORIGIN) == 1));
+    }
+}
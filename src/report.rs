@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::runner::{DayRun, PartRun};
+
+/// Print every part as a JSON array of objects.
+pub fn print_json(runs: &[DayRun]) -> Result<()> {
+    let records: Vec<Value> = runs
+        .iter()
+        .flat_map(|day_run| day_run.parts.iter().map(|run| json_record(day_run, run)))
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&records)?);
+    Ok(())
+}
+
+/// Each part repeats the time spent parsing the input of its day, since both parts share it.
+fn json_record(day_run: &DayRun, run: &PartRun) -> Value {
+    json!({
+        "year": run.year,
+        "day": run.day,
+        "part": run.part,
+        "answer": run.outcome.answer().map(ToString::to_string),
+        "status": run.outcome.status(),
+        "parse_ns": nanos(day_run.parse_elapsed),
+        "duration_ns": nanos(run.elapsed),
+        "error": run.outcome.error(),
+        "check": run.verdict.as_ref().map(ToString::to_string),
+    })
+}
+
+/// Print every part as CSV with a header row.
+pub fn print_csv(runs: &[DayRun]) {
+    println!("year,day,part,answer,status,parse_ns,duration_ns,error,check");
+    for (day_run, run) in runs
+        .iter()
+        .flat_map(|day_run| day_run.parts.iter().map(move |run| (day_run, run)))
+    {
+        let fields = [
+            run.year.to_string(),
+            run.day.to_string(),
+            run.part.to_string(),
+            run.outcome
+                .answer()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            run.outcome.status().to_string(),
+            nanos(day_run.parse_elapsed).to_string(),
+            nanos(run.elapsed).to_string(),
+            run.outcome.error().unwrap_or_default().to_string(),
+            run.verdict
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        println!("{}", line.join(","));
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Quote a field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::runner::{DayRun, Outcome, PartRun};
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(super::csv_field("288"), "288");
+        assert_eq!(super::csv_field("a, b"), "\"a, b\"");
+        assert_eq!(super::csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn json_records() {
+        let run = PartRun {
+            year: 2023,
+            day: 6,
+            part: 1,
+            outcome: Outcome::Failed(String::from("bad input")),
+            elapsed: Duration::from_micros(5),
+            verdict: None,
+        };
+        let day_run = DayRun {
+            input_elapsed: Duration::from_micros(1),
+            parse_elapsed: Duration::from_micros(2),
+            parts: Vec::new(),
+        };
+        assert_eq!(
+            super::json_record(&day_run, &run),
+            json!({
+                "year": 2023,
+                "day": 6,
+                "part": 1,
+                "answer": null,
+                "status": "error",
+                "parse_ns": 2000,
+                "duration_ns": 5000,
+                "error": "bad input",
+                "check": null,
+            })
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use std::any::Any;
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::answers::Verdict;
+use crate::cli::Part;
+use crate::solver::{Answer, DynSolver, Registration, Unsolved};
+
+/// A registered solution for one day of a puzzle year.
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub solver: Box<dyn DynSolver>,
+}
+
+/// Every registered solution, in calendar order.
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    /// Create the solvers of the registry generated by `build.rs`, which is already sorted.
+    pub fn new(solutions: &[Registration]) -> Self {
+        let entries = solutions
+            .iter()
+            .map(|registration| Entry {
+                year: registration.year,
+                day: registration.day,
+                solver: (registration.solver)(),
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Returns the entries matching the given year and day. A missing year or day matches all.
+    pub fn select(&self, year: Option<u16>, day: Option<u8>) -> Vec<&Entry> {
+        self.entries
+            .iter()
+            .filter(|entry| year.is_none_or(|year| entry.year == year))
+            .filter(|entry| day.is_none_or(|day| entry.day == day))
+            .collect()
+    }
+}
+
+/// What happened when a part was run.
+pub enum Outcome {
+    Solved(Answer),
+    Unsolved,
+    MissingInput(String),
+    Failed(String),
+}
+
+impl Outcome {
+    pub fn status(&self) -> &'static str {
+        match self {
+            Outcome::Solved(_) => "ok",
+            Outcome::Unsolved => "unsolved",
+            Outcome::MissingInput(_) => "no input",
+            Outcome::Failed(_) => "error",
+        }
+    }
+
+    pub fn answer(&self) -> Option<&Answer> {
+        match self {
+            Outcome::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    /// The reason no answer was produced, if there was one.
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Outcome::MissingInput(error) | Outcome::Failed(error) => Some(error),
+            Outcome::Solved(_) | Outcome::Unsolved => None,
+        }
+    }
+}
+
+/// The result of running one part of one day.
+pub struct PartRun {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+    /// Set when the answer has been checked against the answers store.
+    pub verdict: Option<Verdict>,
+}
+
+impl PartRun {
+    /// Describes the outcome in a single line, like `2023 day 6 part 1: 288 (12.40µs)`.
+    pub fn describe(&self) -> String {
+        let prefix = format!("{} day {} part {}", self.year, self.day, self.part);
+        let description = match &self.outcome {
+            Outcome::Solved(answer) => format!("{prefix}: {answer} ({:.2?})", self.elapsed),
+            Outcome::Unsolved => format!("{prefix}: there is no solution for this part yet"),
+            Outcome::MissingInput(error) | Outcome::Failed(error) => format!("{prefix}: {error}"),
+        };
+        match &self.verdict {
+            Some(verdict) => format!("{description} [{verdict}]"),
+            None => description,
+        }
+    }
+}
+
+/// Where the puzzle input of a day comes from.
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl InputSource {
+    /// The conventional location of the input for a day, e.g. `inputs/2023/day05.txt`.
+    pub fn default_for(year: u16, day: u8) -> Self {
+        InputSource::File(PathBuf::from(format!("inputs/{year}/day{day:02}.txt")))
+    }
+
+    /// Read from a path given on the command line, where `-` means stdin.
+    pub fn from_path(path: &Path) -> Self {
+        if path == Path::new("-") {
+            InputSource::Stdin
+        } else {
+            InputSource::File(path.to_path_buf())
+        }
+    }
+}
+
+/// The result of running the requested parts of one day.
+pub struct DayRun {
+    /// Time spent reading the input before any part was run.
+    pub input_elapsed: Duration,
+    /// Time spent parsing the input, which is shared by both parts.
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/// Read the input, timing how long it took.
+///
+/// # Errors
+/// Returns a readable message if the input cannot be read.
+pub fn load_input(source: &InputSource) -> Result<(String, Duration), String> {
+    let start = Instant::now();
+    let input = match source {
+        InputSource::File(path) => read_to_string(path)
+            .map_err(|error| format!("cannot read {}: {error}", path.display()))?,
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|error| format!("cannot read stdin: {error}"))?;
+            input
+        }
+        InputSource::Text(text) => text.clone(),
+    };
+    Ok((input, start.elapsed()))
+}
+
+/// Parse the input for a day, timing how long it took.
+pub fn parse_input(entry: &Entry, input: &str) -> (anyhow::Result<Box<dyn Any>>, Duration) {
+    let start = Instant::now();
+    let parsed = entry.solver.parse_input(input);
+    (parsed, start.elapsed())
+}
+
+/// Run one part of a day on its parsed input, timing only the call into the solver.
+pub fn run_part(entry: &Entry, parsed: &dyn Any, part: u8) -> PartRun {
+    let start = Instant::now();
+    let result = entry.solver.solve(parsed, part);
+    let elapsed = start.elapsed();
+    let outcome = match result {
+        Ok(answer) => Outcome::Solved(answer),
+        Err(error) if error.is::<Unsolved>() => Outcome::Unsolved,
+        Err(error) => Outcome::Failed(format!("{error:#}")),
+    };
+    PartRun {
+        year: entry.year,
+        day: entry.day,
+        part,
+        outcome,
+        elapsed,
+        verdict: None,
+    }
+}
+
+/// Run the requested parts of one day against the given input, which is parsed once for both.
+pub fn run(entry: &Entry, source: &InputSource, part: Part) -> DayRun {
+    let (input, input_elapsed) = match load_input(source) {
+        Ok(loaded) => loaded,
+        Err(message) => {
+            return DayRun {
+                input_elapsed: Duration::ZERO,
+                parse_elapsed: Duration::ZERO,
+                parts: unsolved_parts(entry, part, || Outcome::MissingInput(message.clone())),
+            }
+        }
+    };
+    let (parsed, parse_elapsed) = parse_input(entry, &input);
+    let parts = match parsed {
+        Ok(parsed) => part
+            .numbers()
+            .map(|number| run_part(entry, parsed.as_ref(), number))
+            .collect(),
+        Err(error) => unsolved_parts(entry, part, || Outcome::Failed(format!("{error:#}"))),
+    };
+    DayRun {
+        input_elapsed,
+        parse_elapsed,
+        parts,
+    }
+}
+
+/// The runs of parts that could not be started, each with the same outcome.
+fn unsolved_parts(entry: &Entry, part: Part, outcome: impl Fn() -> Outcome) -> Vec<PartRun> {
+    part.numbers()
+        .map(|number| PartRun {
+            year: entry.year,
+            day: entry.day,
+            part: number,
+            outcome: outcome(),
+            elapsed: Duration::ZERO,
+            verdict: None,
+        })
+        .collect()
+}
+
+/// Print a table of every part that was run, followed by the total time spent.
+pub fn print_summary(runs: &[DayRun]) {
+    let verified = runs
+        .iter()
+        .flat_map(|day_run| &day_run.parts)
+        .any(|run| run.verdict.is_some());
+    let mut header = vec!["Year", "Day", "Part", "Answer", "Status", "Time"];
+    if verified {
+        header.push("Check");
+    }
+    let rows: Vec<Vec<String>> = runs
+        .iter()
+        .flat_map(|day_run| &day_run.parts)
+        .map(|run| {
+            let answer = run
+                .outcome
+                .answer()
+                .map_or_else(|| String::from("-"), Answer::to_string);
+            let mut row = vec![
+                run.year.to_string(),
+                run.day.to_string(),
+                run.part.to_string(),
+                answer,
+                run.outcome.status().to_string(),
+                format!("{:.2?}", run.elapsed),
+            ];
+            if let Some(verdict) = &run.verdict {
+                row.push(verdict.to_string());
+            }
+            row
+        })
+        .collect();
+
+    println!();
+    print_table(&header, &rows);
+    let part_count = rows.len();
+    let solving: Duration = runs
+        .iter()
+        .flat_map(|day_run| &day_run.parts)
+        .map(|run| run.elapsed)
+        .sum();
+    let reading: Duration = runs.iter().map(|day_run| day_run.input_elapsed).sum();
+    let parsing: Duration = runs.iter().map(|day_run| day_run.parse_elapsed).sum();
+    println!(
+        "\nRan {part_count} parts in {solving:.2?} ({reading:.2?} reading input, \
+        {parsing:.2?} parsing)"
+    );
+}
+
+/// Print rows of cells as a table with left-aligned, padded columns.
+pub fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let format_row = |row: &mut dyn Iterator<Item = &str>| {
+        row.zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<String>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", format_row(&mut header.iter().copied()));
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    println!("{}", separator.join("-+-"));
+    for row in rows {
+        println!("{}", format_row(&mut row.iter().map(String::as_str)));
+    }
+}
@@ -1,8 +1,148 @@
-#[macro_export]
-macro_rules! solution {
-    ($expression:expr) => {
-        pub fn solve(path: PathBuf) {
-            ($expression)(path)
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use anyhow::Result;
+
+/// A solution to one day of a puzzle year.
+///
+/// The puzzle input is parsed once, and both parts solve from the parsed input, so the runner
+/// can time parsing separately from each part. Parts return their answer rather than printing
+/// it, so the runner decides how answers are reported.
+pub trait Solver {
+    /// The puzzle input once it has been parsed.
+    type Parsed;
+
+    /// # Errors
+    /// Returns an error if the input is malformed.
+    fn parse(&self, input: &str) -> Result<Self::Parsed>;
+
+    /// # Errors
+    /// Returns an error if the input cannot be solved.
+    fn part1(&self, parsed: &Self::Parsed) -> Result<Answer>;
+
+    /// Days that only have the first part solved can rely on this default.
+    ///
+    /// # Errors
+    /// Returns an error if the input cannot be solved or the part is [`Unsolved`].
+    fn part2(&self, _parsed: &Self::Parsed) -> Result<Answer> {
+        Err(Unsolved.into())
+    }
+}
+
+/// A [`Solver`] with its parsed input type hidden, so the solvers of every day can be kept
+/// together in the registry.
+pub trait DynSolver {
+    /// # Errors
+    /// Returns an error if the input is malformed.
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any>>;
+
+    /// Solve part 1 or 2 from input returned by [`DynSolver::parse_input`].
+    ///
+    /// # Errors
+    /// Returns an error if the input cannot be solved or the part is [`Unsolved`].
+    ///
+    /// # Panics
+    /// Panics if the input was parsed by a different solver.
+    fn solve(&self, parsed: &dyn Any, part: u8) -> Result<Answer>;
+}
+
+impl<S> DynSolver for S
+where
+    S: Solver,
+    S::Parsed: 'static,
+{
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(self.parse(input)?))
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: u8) -> Result<Answer> {
+        let parsed = parsed
+            .downcast_ref()
+            .expect("the input should be parsed by the same solver");
+        if part == 1 {
+            self.part1(parsed)
+        } else {
+            self.part2(parsed)
         }
-    };
+    }
+}
+
+/// A day's solution, as listed in the registry generated by `build.rs`.
+pub struct Registration {
+    pub year: u16,
+    pub day: u8,
+    pub solver: fn() -> Box<dyn DynSolver>,
+}
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Signed(value.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Unsigned(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+
+/// Returned by a part that has not been solved yet.
+#[derive(Debug)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "this part has not been solved yet")
+    }
 }
+
+impl Error for Unsolved {}
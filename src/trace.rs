@@ -0,0 +1,145 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// How much diagnostic output to write. Set once from `--verbose` before any solver runs.
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+/// The days allowed to write diagnostic output, or every day if this is empty.
+static DAY_FILTERS: OnceLock<Vec<DayFilter>> = OnceLock::new();
+
+/// How detailed a message is. Each `-v` shows one more level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// A summary of what a solver is doing, like one line per input item.
+    Debug = 1,
+    /// Every step of a solver.
+    Trace = 2,
+}
+
+/// Write a message to stderr at [`Level::Debug`], if that level is enabled for the calling
+/// module. The arguments are only formatted when the message is written.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::trace::log($crate::trace::Level::Debug, module_path!(), format_args!($($arg)*))
+    };
+}
+
+/// Like [`debug!`], but at [`Level::Trace`].
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::trace::log($crate::trace::Level::Trace, module_path!(), format_args!($($arg)*))
+    };
+}
+
+pub(crate) use {debug, trace};
+
+/// Enable diagnostic output up to `verbosity`, only from the given days if there are any.
+/// Naming days asks for their output, so it enables at least [`Level::Debug`].
+pub fn init(verbosity: u8, day_filters: Vec<DayFilter>) {
+    let verbosity = if day_filters.is_empty() {
+        verbosity
+    } else {
+        verbosity.max(Level::Debug as u8)
+    };
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+    // Only the first call sets the filters, and `main` only calls this once.
+    let _ = DAY_FILTERS.set(day_filters);
+}
+
+/// Whether messages at `level` from `module` are written. Use this to skip expensive work that
+/// is only needed for a message.
+#[must_use]
+pub fn enabled(level: Level, module: &str) -> bool {
+    if VERBOSITY.load(Ordering::Relaxed) < level as u8 {
+        return false;
+    }
+    let filters = DAY_FILTERS.get().map_or(&[][..], Vec::as_slice);
+    filters.is_empty() || filters.iter().any(|filter| filter.matches(module))
+}
+
+/// Write a message to stderr, prefixed by the module that wrote it, if its level is enabled.
+pub fn log(level: Level, module: &str, message: fmt::Arguments) {
+    if enabled(level, module) {
+        let module = module.split_once("::").map_or(module, |(_, rest)| rest);
+        eprintln!("[{module}] {message}");
+    }
+}
+
+/// A year, or a single day of a year, that is allowed to write diagnostic output. Written like
+/// `2023` or `2023/5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayFilter {
+    year: u16,
+    day: Option<u8>,
+}
+
+impl DayFilter {
+    /// Whether a module path like `advent_of_code::year2023::day5` belongs to this filter.
+    fn matches(self, module: &str) -> bool {
+        let mut segments = module.split("::");
+        let year = segments.find_map(|segment| segment.strip_prefix("year")?.parse::<u16>().ok());
+        let day = segments
+            .next()
+            .and_then(|segment| segment.strip_prefix("day")?.parse::<u8>().ok());
+        year == Some(self.year) && self.day.is_none_or(|filter_day| day == Some(filter_day))
+    }
+}
+
+impl FromStr for DayFilter {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (year, day) = match text.split_once('/') {
+            Some((year, day)) => (year, Some(day)),
+            None => (text, None),
+        };
+        let year = year
+            .parse()
+            .map_err(|_| format!("'{year}' is not a year, expected e.g. 2023 or 2023/5"))?;
+        let day = day
+            .map(str::parse)
+            .transpose()
+            .map_err(|_| format!("'{text}' does not name a day, expected e.g. 2023/5"))?;
+        Ok(Self { year, day })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DayFilter;
+
+    #[test]
+    fn parsing_filters() {
+        assert_eq!(
+            "2023/5".parse(),
+            Ok(DayFilter {
+                year: 2023,
+                day: Some(5)
+            })
+        );
+        assert_eq!(
+            "2015".parse(),
+            Ok(DayFilter {
+                year: 2015,
+                day: None
+            })
+        );
+        assert!("2023/x".parse::<DayFilter>().is_err());
+        assert!("day5".parse::<DayFilter>().is_err());
+    }
+
+    #[test]
+    fn matching_modules() {
+        let day5: DayFilter = "2023/5".parse().unwrap();
+        assert!(day5.matches("advent_of_code::year2023::day5"));
+        assert!(!day5.matches("advent_of_code::year2023::day15"));
+        assert!(!day5.matches("advent_of_code::year2015::day5"));
+        assert!(!day5.matches("advent_of_code::interval"));
+
+        let year: DayFilter = "2015".parse().unwrap();
+        assert!(year.matches("advent_of_code::year2015::day5"));
+        assert!(year.matches("advent_of_code::year2015::day5::tests"));
+        assert!(!year.matches("advent_of_code::year2023::day5"));
+    }
+}
@@ -1,7 +1,94 @@
-use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
 
-use crate::solution;
+use crate::solver::{Answer, Solver};
 
-solution!(|_| {
-    println!("I am a solution!");
-});
+#[derive(Default)]
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Vec<i64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        floor_changes(input)
+    }
+
+    fn part1(&self, changes: &Self::Parsed) -> Result<Answer> {
+        Ok(changes.iter().sum::<i64>().into())
+    }
+
+    fn part2(&self, changes: &Self::Parsed) -> Result<Answer> {
+        Ok(first_basement_position(changes)?.into())
+    }
+}
+
+/// Read each instruction as a change of floor: `(` goes up one floor and `)` goes down one.
+fn floor_changes(input: &str) -> Result<Vec<i64>> {
+    input
+        .trim_end()
+        .chars()
+        .enumerate()
+        .map(|(index, c)| match c {
+            '(' => Ok(1),
+            ')' => Ok(-1),
+            _ => bail!(
+                "position {}: expected '(' or ')', found '{}'",
+                index + 1,
+                c.escape_default()
+            ),
+        })
+        .collect()
+}
+
+/// The position, counting from 1, of the first instruction that takes Santa below the ground
+/// floor.
+fn first_basement_position(changes: &[i64]) -> Result<usize> {
+    changes
+        .iter()
+        .scan(0, |floor, change| {
+            *floor += change;
+            Some(*floor)
+        })
+        .position(|floor| floor < 0)
+        .map(|index| index + 1)
+        .context("Santa never enters the basement")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn final_floor(input: &str) -> i64 {
+        floor_changes(input).unwrap().iter().sum()
+    }
+
+    #[test]
+    fn final_floors() {
+        assert_eq!(final_floor("(())"), 0);
+        assert_eq!(final_floor("()()"), 0);
+        assert_eq!(final_floor("((("), 3);
+        assert_eq!(final_floor("(()(()("), 3);
+        assert_eq!(final_floor("))((((("), 3);
+        assert_eq!(final_floor("())"), -1);
+        assert_eq!(final_floor("))("), -1);
+        assert_eq!(final_floor(")))"), -3);
+        assert_eq!(final_floor(")())())\n"), -3);
+    }
+
+    #[test]
+    fn entering_the_basement() {
+        let position = |input| first_basement_position(&floor_changes(input).unwrap());
+        assert_eq!(position(")").unwrap(), 1);
+        assert_eq!(position("()())").unwrap(), 5);
+        assert!(position("(()").is_err());
+    }
+
+    #[test]
+    fn invalid_instructions() {
+        let error = floor_changes("(()x)").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "position 4: expected '(' or ')', found 'x'"
+        );
+        assert!(floor_changes("((\n))").is_err());
+    }
+}
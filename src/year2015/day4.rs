@@ -1,29 +1,31 @@
-use std::fs::read_to_string;
-use std::path::PathBuf;
-
-use crate::solution;
-
-solution!(|path| {
-    let key_prefix = read_to_string(path).unwrap();
-    let result = lowest_md5_prefix(&key_prefix, 5);
-    println!("The lowest number is {result} for 5 zeroes");
-    let result = lowest_md5_prefix(&key_prefix, 6);
-    println!("The lowest number is {result} for 6 zeroes");
-});
-
-fn lowest_md5_prefix(prefix: &str, zero_count: usize) -> u32 {
-    let mut suffix = 0;
-    loop {
-        let input = format!("{prefix}{suffix}");
-        let hash = format!("{:x}", md5::compute(input));
-        let num_leading_zeroes = hash.bytes().into_iter().take_while(|b| b == &b'0').count();
-        if num_leading_zeroes == zero_count {
-            break;
-        }
-        suffix += 1;
+use anyhow::Result;
+
+use crate::hash_search::{leading_zero_nibbles, HashSearch};
+use crate::solver::{Answer, Solver};
+
+#[derive(Default)]
+pub struct Solution;
+
+impl Solver for Solution {
+    /// The secret key.
+    type Parsed = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(&self, key: &Self::Parsed) -> Result<Answer> {
+        Ok(lowest_md5_prefix(key, 5).into())
     }
 
-    suffix
+    fn part2(&self, key: &Self::Parsed) -> Result<Answer> {
+        Ok(lowest_md5_prefix(key, 6).into())
+    }
+}
+
+/// The lowest number that, after the secret key, gives a hash starting with `zero_count` zeroes.
+fn lowest_md5_prefix(prefix: &str, zero_count: u32) -> u64 {
+    HashSearch::new(prefix).first_match(|digest| leading_zero_nibbles(digest) >= zero_count)
 }
 
 #[cfg(test)]
@@ -32,6 +34,24 @@ mod tests {
 
     #[test]
     fn examples() {
-        assert_eq!(lowest_md5_prefix("abcdef", 5), 609043);
+        assert_eq!(lowest_md5_prefix("abcdef", 5), 609_043);
+        assert_eq!(lowest_md5_prefix("pqrstuv", 5), 1_048_970);
+    }
+
+    #[test]
+    fn matches_a_brute_force_search() {
+        // For `pqrstuv`, the first hashes with at least one and at least three zeroes have one
+        // more zero than asked for, so they are only found if extra zeroes still match.
+        for key in ["abcdef", "pqrstuv"] {
+            for zeroes in 1..=4 {
+                let expected = (0..u64::MAX)
+                    .find(|i| {
+                        format!("{:x}", md5::compute(format!("{key}{i}")))
+                            .starts_with(&"0".repeat(zeroes as usize))
+                    })
+                    .unwrap();
+                assert_eq!(lowest_md5_prefix(key, zeroes), expected, "{key}, {zeroes}");
+            }
+        }
     }
 }
@@ -1,22 +1,38 @@
-use std::fs::read_to_string;
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::collections::HashMap;
 
-use crate::solution;
+use anyhow::Result;
+
+use crate::solver::{Answer, Solver};
+use crate::trace::debug;
 
 const VOWELS: [char; 5] = ['a', 'e', 'i', 'o', 'u'];
 const BANNED_SUBSTRINGS: [&str; 4] = ["ab", "cd", "pq", "xy"];
 
-solution!(|path| {
-    let content = read_to_string(path).unwrap();
-    let count = content
-        .lines()
-        .filter(|line| is_nice_string(line.to_string()))
-        .count();
-    println!("There are {count} nice strings.");
-});
+#[derive(Default)]
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.lines().map(str::to_owned).collect())
+    }
+
+    fn part1(&self, strings: &Self::Parsed) -> Result<Answer> {
+        let count = strings.iter().filter(|line| is_nice_string(line)).count();
+        Ok(count.into())
+    }
+
+    fn part2(&self, strings: &Self::Parsed) -> Result<Answer> {
+        let count = strings
+            .iter()
+            .filter(|line| is_nice_string_p2(line))
+            .count();
+        Ok(count.into())
+    }
+}
 
-fn is_nice_string(input: String) -> bool {
+fn is_nice_string(input: &str) -> bool {
     let chars: Vec<char> = input.chars().collect();
     let mut num_vowels = 0;
     let mut has_double_letter = false;
@@ -37,32 +53,24 @@ fn is_nice_string(input: String) -> bool {
             has_double_letter = true;
         }
     }
-    println!("Input: {input}, Num vowels: {num_vowels}, Has Double Letter: {has_double_letter}");
+    debug!("{input}: {num_vowels} vowels, double letter: {has_double_letter}");
     num_vowels >= 3 && has_double_letter
 }
 
+/// A nice string has a pair of letters that appears twice without overlapping, like `xyxy`, and
+/// a letter that repeats with exactly one letter between, like `xyx`.
 fn is_nice_string_p2(input: &str) -> bool {
-    let mut has_double_double_letters = false;
-    let mut has_repeated_char = false;
-    for (index, _) in input.char_indices() {
-        if index == input.len() - 3 {
-            break;
-        }
-        let window = &input[index..index + 4];
-        println!("Input: {input}, window: {window:?}");
-        let window = window.as_bytes();
-        if window.len() < 4 {
-            break;
-        }
-        if !has_double_double_letters
-            && format!("{}{}", window[0], window[1]) == format!("{}{}", window[2], window[3])
-        {
-            has_double_double_letters = true;
-        }
-        has_repeated_char = window[0] == window[2] || window[1] == window[3];
-    }
+    let bytes = input.as_bytes();
+    has_repeated_pair(bytes) && bytes.windows(3).any(|window| window[0] == window[2])
+}
 
-    has_double_double_letters && has_repeated_char
+/// Whether any pair of letters appears twice without the two copies overlapping.
+fn has_repeated_pair(bytes: &[u8]) -> bool {
+    let mut first_seen = HashMap::new();
+    bytes.windows(2).enumerate().any(|(index, pair)| {
+        let first = *first_seen.entry(pair).or_insert(index);
+        index >= first + 2
+    })
 }
 
 #[cfg(test)]
@@ -71,18 +79,24 @@ mod tests {
 
     #[test]
     fn examples() {
-        assert_eq!(is_nice_string("ugknbfddgicrmopn".to_string()), true);
-        assert_eq!(is_nice_string("aaa".to_string()), true);
-        assert_eq!(is_nice_string("jchzalrnumimnmhp".to_string()), false);
-        assert_eq!(is_nice_string("haegwjzuvuyypxyu".to_string()), false);
-        assert_eq!(is_nice_string("dvszwmarrgswjxmb".to_string()), false);
+        assert!(is_nice_string("ugknbfddgicrmopn"));
+        assert!(is_nice_string("aaa"));
+        assert!(!is_nice_string("jchzalrnumimnmhp"));
+        assert!(!is_nice_string("haegwjzuvuyypxyu"));
+        assert!(!is_nice_string("dvszwmarrgswjxmb"));
     }
 
     #[test]
     fn examples_p2() {
-        assert_eq!(is_nice_string_p2("qjhvhtzxzqqjkmpb"), true);
-        assert_eq!(is_nice_string_p2("xxyxx"), true);
-        assert_eq!(is_nice_string_p2("uurcxstgmygtbstg"), false);
-        assert_eq!(is_nice_string_p2("ieodomkazucvgmuy"), false);
+        assert!(is_nice_string_p2("qjhvhtzxzqqjkmpb"));
+        assert!(is_nice_string_p2("xxyxx"));
+        assert!(!is_nice_string_p2("uurcxstgmygtbstg"));
+        assert!(!is_nice_string_p2("ieodomkazucvgmuy"));
+        // The two copies of `aa` in `aaa` overlap, but in `aaaa` they do not.
+        assert!(!is_nice_string_p2("aaa"));
+        assert!(is_nice_string_p2("aaaa"));
+        assert!(is_nice_string_p2("xyxy"));
+        assert!(!is_nice_string_p2("aabcdefgaa"));
+        assert!(!is_nice_string_p2(""));
     }
 }
@@ -1,3 +0,0 @@
-pub mod day1;
-pub mod day4;
-pub mod day5;
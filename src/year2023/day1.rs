@@ -1,83 +1,103 @@
-use std::fs::File;
-use std::path::PathBuf;
+use anyhow::{Context, Result};
 
-use crate::io::LineExtractor;
-use crate::solution;
+use crate::matcher::PatternMatcher;
+use crate::parse;
+use crate::solver::{Answer, Solver};
 
-const NUMBERS: [(&str, &str); 9] = [
-    ("one", "1"),
-    ("two", "2"),
-    ("three", "3"),
-    ("four", "4"),
-    ("five", "5"),
-    ("six", "6"),
-    ("seven", "7"),
-    ("eight", "8"),
-    ("nine", "9"),
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
 
-solution!(|path| {
-    let total: i32 = File::open(path)
-        .expect("Cannot read the file")
-        .lines()
-        .map(|line| parse_line(&line.unwrap()))
-        .sum();
-    println!("The total is {total}");
-});
+#[derive(Default)]
+pub struct Solution;
 
-/// Given a line in a file, find the first and last numbers in the line. The digits are combined
-/// to form a two digit number.
-///
-/// # Examples
-/// ```
-/// let line1 = String::from("trom7bone");
-/// let line2 = String::from("asdf4jkdsqseven");
-/// let line3 = String::from("asdasfourdsqwefiveasdastwobar");
-/// assert_eq!(parse_line(line1), 77);
-/// assert_eq!(parse_line(line2), 47);
-/// assert_eq!(parse_line(line3), 42);
-/// ```
-fn parse_line(line: &str) -> i32 {
-    let numbers = extract_numbers(line);
-    let first_number = numbers.first().unwrap();
-    let second_number = numbers.last().unwrap();
-    format!("{first_number}{second_number}")
-        .parse::<i32>()
-        .unwrap()
-}
+impl Solver for Solution {
+    /// The calibration document. The parts disagree on what counts as a number, so each part
+    /// reads the lines itself.
+    type Parsed = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.to_owned())
+    }
 
-/// Given a string, extract all number literals and written out numbers.
-fn extract_numbers(line: &str) -> Vec<String> {
-    line.char_indices()
-        .filter_map(|(index, c)| find_number_string(index, c, line))
-        .collect()
+    fn part1(&self, document: &Self::Parsed) -> Result<Answer> {
+        let numbers = number_matcher(false);
+        let total: i32 = parse::lines(document, |line| parse_line(line, &numbers))?
+            .iter()
+            .sum();
+        Ok(total.into())
+    }
+
+    fn part2(&self, document: &Self::Parsed) -> Result<Answer> {
+        let numbers = number_matcher(true);
+        let total: i32 = parse::lines(document, |line| parse_line(line, &numbers))?
+            .iter()
+            .sum();
+        Ok(total.into())
+    }
 }
 
-/// Given an index in a string, the character at that index, and a string, determine if that
-/// character is either a number or the beginning of a word of a number.
-fn find_number_string(index: usize, c: char, line: &str) -> Option<String> {
-    match c {
-        '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => Some(c.to_string()),
-        'o' | 't' | 'f' | 's' | 'e' | 'n' => get_word_of_number(line, index),
-        _ => None,
+/// Match the digits `1` to `9`, and optionally their names, like `one` or `nine`.
+fn number_matcher(include_words: bool) -> PatternMatcher<i32> {
+    let mut patterns: Vec<(String, i32)> =
+        (1..=9).map(|value| (value.to_string(), value)).collect();
+    if include_words {
+        patterns.extend(
+            WORDS
+                .iter()
+                .zip(1..)
+                .map(|(word, value)| ((*word).to_string(), value)),
+        );
     }
+    PatternMatcher::new(patterns)
 }
 
-/// Given a line and an index in that line, search for words that describe numbers,
-/// such as `"one"`, `"eight"`, or `"nine"`.
-///
-/// These numbers are at most five characters long, so this function takes the index and looks five
-/// characters further. If this string slice begins with any number-word, then the corresponding
-/// number is returned as string.
-fn get_word_of_number(line: &str, index: usize) -> Option<String> {
-    let last_index = line.len().min(index + 5);
-    match line.get(index..last_index) {
-        Some(slice) => NUMBERS.iter().find_map(|(number, value)| {
-            if slice.starts_with(number) {
-                return Some((*value).to_string());
-            }
-            None
-        }),
-        None => None,
+/// Given a line in a file, find the first and last numbers in the line. The digits are combined
+/// to form a two digit number. Numbers may overlap, so `eightwo` ends in `two` when words count.
+fn parse_line(line: &str, numbers: &PatternMatcher<i32>) -> Result<i32> {
+    let first = numbers.first(line).context("the line has no numbers")?;
+    let last = numbers.last(line).context("the line has no numbers")?;
+    Ok(first.value * 10 + last.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_only() {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        let document = Solution.parse(input).unwrap();
+        assert_eq!(Solution.part1(&document).unwrap().to_string(), "142");
+        let digits = number_matcher(false);
+        assert_eq!(parse_line("two1nine", &digits).unwrap(), 11);
+        assert!(parse_line("eightwothree", &digits).is_err());
+    }
+
+    #[test]
+    fn digits_and_words() {
+        let input = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+        let document = Solution.parse(input).unwrap();
+        assert_eq!(Solution.part2(&document).unwrap().to_string(), "281");
+        let numbers = number_matcher(true);
+        assert_eq!(parse_line("eightwo", &numbers).unwrap(), 82);
+        assert_eq!(parse_line("trom7bone", &numbers).unwrap(), 71);
+        assert_eq!(parse_line("oneight", &numbers).unwrap(), 18);
+    }
+
+    #[test]
+    fn lines_without_numbers() {
+        let document = Solution.parse("1two\nnothing").unwrap();
+        let error = Solution.part2(&document).unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "line 2: 'nothing': the line has no numbers"
+        );
     }
 }
@@ -0,0 +1,268 @@
+use anyhow::{bail, Context, Result};
+
+use crate::grid::Grid;
+use crate::point::{Direction, Point};
+use crate::solver::{Answer, Solver};
+
+#[derive(Default)]
+pub struct Solution;
+
+impl Solver for Solution {
+    /// The maze with the pipe under the start filled in, and the start.
+    type Parsed = (Grid<Tile>, Point);
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        parse_maze(input)
+    }
+
+    fn part1(&self, (maze, start): &Self::Parsed) -> Result<Answer> {
+        Ok((trace_loop(maze, *start)?.len() / 2).into())
+    }
+
+    fn part2(&self, (maze, start): &Self::Parsed) -> Result<Answer> {
+        let main_loop = trace_loop(maze, *start)?;
+        let by_rays = enclosed_by_ray_casting(maze, &main_loop)?;
+        let by_area = enclosed_by_area(&main_loop);
+        if by_rays != by_area {
+            bail!("ray casting finds {by_rays} enclosed tiles, but the area gives {by_area}");
+        }
+        Ok(by_rays.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Ground,
+    /// The animal's starting position, which hides an unknown pipe.
+    Start,
+    /// A pipe connecting two neighbouring tiles.
+    Pipe(Direction, Direction),
+}
+
+impl Tile {
+    fn connects(self, direction: Direction) -> bool {
+        matches!(self, Tile::Pipe(a, b) if a == direction || b == direction)
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        use Direction::{East, North, South, West};
+        Ok(match c {
+            '|' => Tile::Pipe(North, South),
+            '-' => Tile::Pipe(East, West),
+            'L' => Tile::Pipe(North, East),
+            'J' => Tile::Pipe(North, West),
+            '7' => Tile::Pipe(South, West),
+            'F' => Tile::Pipe(East, South),
+            '.' => Tile::Ground,
+            'S' => Tile::Start,
+            _ => bail!("'{c}' is not a pipe"),
+        })
+    }
+}
+
+/// Parse the maze, replacing the start with the pipe hidden under it.
+fn parse_maze(input: &str) -> Result<(Grid<Tile>, Point)> {
+    let mut maze = Grid::parse_with(input, Tile::try_from)?;
+    let start = maze
+        .iter()
+        .find(|(_, tile)| **tile == Tile::Start)
+        .map(|(point, _)| point)
+        .context("the maze has no starting position")?;
+    let hidden = hidden_pipe(&maze, start)?;
+    *maze
+        .get_mut(start)
+        .context("the start is outside of the maze")? = hidden;
+    Ok((maze, start))
+}
+
+/// Work out the pipe under the start from the two neighbouring pipes that connect to it.
+fn hidden_pipe(maze: &Grid<Tile>, start: Point) -> Result<Tile> {
+    let connected: Vec<Direction> = Direction::CARDINALS
+        .into_iter()
+        .filter(|direction| {
+            maze.get(start.step(*direction))
+                .is_some_and(|tile| tile.connects(direction.opposite()))
+        })
+        .collect();
+    match connected[..] {
+        [first, second] => Ok(Tile::Pipe(first, second)),
+        _ => bail!(
+            "the start should connect to exactly 2 pipes, but it connects to {}",
+            connected.len()
+        ),
+    }
+}
+
+/// Follow the pipes from the start until they lead back to it, returning each tile on the way.
+fn trace_loop(maze: &Grid<Tile>, start: Point) -> Result<Vec<Point>> {
+    let Some(Tile::Pipe(mut heading, _)) = maze.get(start).copied() else {
+        bail!("the start is not on a pipe");
+    };
+    let mut main_loop = vec![start];
+    let mut position = start.step(heading);
+    while position != start {
+        let came_from = heading.opposite();
+        heading = match maze.get(position) {
+            Some(Tile::Pipe(a, b)) if *a == came_from => *b,
+            Some(Tile::Pipe(a, b)) if *b == came_from => *a,
+            _ => bail!("the pipe breaks off at x {}, y {}", position.x, position.y),
+        };
+        main_loop.push(position);
+        position = position.step(heading);
+    }
+    Ok(main_loop)
+}
+
+/// Count the tiles inside the loop by scanning each row from the left. Crossing a loop tile
+/// that connects north moves between outside and inside. Corners that only connect south do
+/// not, so a run like `L--7` counts as one crossing and `L--J` as none.
+fn enclosed_by_ray_casting(maze: &Grid<Tile>, main_loop: &[Point]) -> Result<usize> {
+    let mut on_loop = Grid::from_cells(
+        maze.width(),
+        maze.height(),
+        vec![false; maze.width() * maze.height()],
+    )?;
+    for point in main_loop {
+        *on_loop
+            .get_mut(*point)
+            .context("the loop leaves the maze")? = true;
+    }
+    let mut enclosed = 0;
+    let mut inside = false;
+    for (point, tile) in maze.iter() {
+        if point.x == 0 {
+            inside = false;
+        }
+        if on_loop.get(point) == Some(&true) {
+            inside ^= tile.connects(Direction::North);
+        } else if inside {
+            enclosed += 1;
+        }
+    }
+    Ok(enclosed)
+}
+
+/// Count the tiles inside the loop from its area. The shoelace formula gives the area of the
+/// polygon through the centres of the loop's tiles, and Pick's theorem turns that area and the
+/// number of tiles on the boundary into the number of tiles strictly inside.
+fn enclosed_by_area(main_loop: &[Point]) -> usize {
+    let next = main_loop.iter().cycle().skip(1);
+    let twice_area = main_loop
+        .iter()
+        .zip(next)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i64>()
+        .unsigned_abs();
+    let boundary = main_loop.len() as u64;
+    // Pick's theorem: area = interior + boundary / 2 - 1.
+    let interior = (twice_area + 2).saturating_sub(boundary) / 2;
+    usize::try_from(interior).expect("the interior is no larger than the maze")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMPLE_LOOP: &str = "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    const COMPLEX_LOOP: &str = "7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ";
+
+    const ENCLOSED: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const SQUEEZED: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    const LARGER: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    const JUNK: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    #[test]
+    fn inferring_the_start() {
+        let (maze, start) = parse_maze(SIMPLE_LOOP).unwrap();
+        assert_eq!(start, Point::new(1, 1));
+        assert_eq!(
+            maze.get(start),
+            Some(&Tile::Pipe(Direction::East, Direction::South))
+        );
+        let (maze, start) = parse_maze(COMPLEX_LOOP).unwrap();
+        assert_eq!(
+            maze.get(start),
+            Some(&Tile::Pipe(Direction::East, Direction::South))
+        );
+        assert!(parse_maze("...\n.S.\n...").is_err());
+        assert!(parse_maze(".|.\n.X.").is_err());
+    }
+
+    #[test]
+    fn farthest_point() {
+        let farthest = |maze| Solution.part1(&parse_maze(maze).unwrap()).unwrap();
+        assert_eq!(farthest(SIMPLE_LOOP).to_string(), "4");
+        assert_eq!(farthest(COMPLEX_LOOP).to_string(), "8");
+    }
+
+    #[test]
+    fn enclosed_tiles() {
+        for (maze, expected) in [
+            (ENCLOSED, 4),
+            (SQUEEZED, 4),
+            (LARGER, 8),
+            (JUNK, 10),
+            (SIMPLE_LOOP, 1),
+        ] {
+            let parsed = parse_maze(maze).unwrap();
+            let (grid, start) = &parsed;
+            let main_loop = trace_loop(grid, *start).unwrap();
+            assert_eq!(enclosed_by_ray_casting(grid, &main_loop).unwrap(), expected);
+            assert_eq!(enclosed_by_area(&main_loop), expected);
+            assert_eq!(
+                Solution.part2(&parsed).unwrap().to_string(),
+                expected.to_string()
+            );
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::{bail, Result};
+
+use crate::grid::Grid;
+use crate::solver::{Answer, Solver};
+
+/// Each part expands every empty row and column of the image into this many.
+pub struct Solution {
+    pub part1_expansion: u64,
+    pub part2_expansion: u64,
+}
+
+impl Default for Solution {
+    fn default() -> Self {
+        Self {
+            part1_expansion: 2,
+            part2_expansion: 1_000_000,
+        }
+    }
+}
+
+impl Solver for Solution {
+    type Parsed = Grid<bool>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        parse_image(input)
+    }
+
+    fn part1(&self, image: &Self::Parsed) -> Result<Answer> {
+        Ok(total_distance(image, self.part1_expansion).into())
+    }
+
+    fn part2(&self, image: &Self::Parsed) -> Result<Answer> {
+        Ok(total_distance(image, self.part2_expansion).into())
+    }
+}
+
+/// Parse an image where `#` is a galaxy and `.` is empty space.
+fn parse_image(input: &str) -> Result<Grid<bool>> {
+    Grid::parse_with(input, |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => bail!("'{c}' is neither a galaxy nor empty space"),
+    })
+}
+
+/// Add up the shortest distances between every pair of galaxies once space has expanded.
+///
+/// Moving along rows and columns, the distance is the sum of the horizontal and vertical
+/// distances, so each axis is handled on its own without building the expanded image.
+fn total_distance(image: &Grid<bool>, expansion: u64) -> u64 {
+    let column_positions = expanded_positions(
+        image.columns().map(|mut column| column.any(|g| *g)),
+        expansion,
+    );
+    let row_positions = expanded_positions(image.rows().map(|row| row.contains(&true)), expansion);
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+    for (y, row) in image.rows().enumerate() {
+        for (x, _) in row.iter().enumerate().filter(|(_, galaxy)| **galaxy) {
+            xs.push(column_positions[x]);
+            ys.push(row_positions[y]);
+        }
+    }
+    pairwise_distance(xs) + pairwise_distance(ys)
+}
+
+/// Where each row or column ends up once every empty one has grown to `expansion` of them.
+fn expanded_positions(occupied: impl Iterator<Item = bool>, expansion: u64) -> Vec<u64> {
+    occupied
+        .scan(0, |position, occupied| {
+            let current = *position;
+            *position += if occupied { 1 } else { expansion };
+            Some(current)
+        })
+        .collect()
+}
+
+/// The sum of the distances between every pair of positions on a line.
+///
+/// Once sorted, each position is further along than all of the positions before it, so its
+/// distances to them are its position times their count, minus their running total.
+fn pairwise_distance(mut positions: Vec<u64>) -> u64 {
+    positions.sort_unstable();
+    let mut total = 0;
+    let mut prefix_sum = 0;
+    for (count, position) in (0..).zip(positions) {
+        total += position * count - prefix_sum;
+        prefix_sum += position;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    fn solution(expansion: u64) -> Solution {
+        Solution {
+            part1_expansion: expansion,
+            ..Solution::default()
+        }
+    }
+
+    #[test]
+    fn expanding_space() {
+        let image = parse_image(EXAMPLE).unwrap();
+        let columns = image.columns().map(|mut column| column.any(|g| *g));
+        assert_eq!(
+            expanded_positions(columns, 2),
+            [0, 1, 2, 4, 5, 6, 8, 9, 10, 12]
+        );
+        assert!(parse_image("#.\n.x").is_err());
+    }
+
+    #[test]
+    fn pairwise_distances() {
+        assert_eq!(pairwise_distance(vec![4, 0, 9]), 4 + 9 + 5);
+        assert_eq!(pairwise_distance(vec![]), 0);
+        assert_eq!(pairwise_distance(vec![3, 3]), 0);
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let image = parse_image(EXAMPLE).unwrap();
+        assert_eq!(
+            Solution::default().part1(&image).unwrap().to_string(),
+            "374"
+        );
+        assert_eq!(solution(10).part1(&image).unwrap().to_string(), "1030");
+        assert_eq!(solution(100).part1(&image).unwrap().to_string(), "8410");
+        let larger = Solution {
+            part2_expansion: 100,
+            ..Solution::default()
+        };
+        assert_eq!(larger.part2(&image).unwrap().to_string(), "8410");
+    }
+}
@@ -1,35 +1,41 @@
-use std::fs::File;
-use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
 
-use crate::io::LineExtractor;
-use crate::solution;
+use crate::parse;
+use crate::solver::{Answer, Solver};
 
 const RED_LIMIT: i32 = 12;
 const GREEN_LIMIT: i32 = 13;
 const BLUE_LIMIT: i32 = 14;
 
-solution!(|path: PathBuf| {
-    let id_total: i32 = File::open(path.clone())
-        .expect("Cannot read the file")
-        .lines()
-        .map(|line| Game::from(line.unwrap()))
-        .filter_map(|game| {
-            if game.is_possible() {
-                Some(game.id)
-            } else {
-                None
-            }
-        })
-        .sum();
-    println!("The total of the possible game IDs is {id_total}");
+#[derive(Default)]
+pub struct Solution;
 
-    let power_total: i32 = File::open(path)
-        .expect("Cannot read the file")
-        .lines()
-        .map(|line| Game::from(line.unwrap()).power())
-        .sum();
-    println!("The total power of the games is {power_total}");
-});
+impl Solver for Solution {
+    type Parsed = Vec<Game>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        parse::lines(input, |line| Game::try_from(line))
+    }
+
+    fn part1(&self, games: &Self::Parsed) -> Result<Answer> {
+        let id_total: i32 = games
+            .iter()
+            .filter_map(|game| {
+                if game.is_possible() {
+                    Some(game.id)
+                } else {
+                    None
+                }
+            })
+            .sum();
+        Ok(id_total.into())
+    }
+
+    fn part2(&self, games: &Self::Parsed) -> Result<Answer> {
+        let power_total: i32 = games.iter().map(Game::power).sum();
+        Ok(power_total.into())
+    }
+}
 
 /// Represents a game an elf wants to play with us.
 ///
@@ -40,7 +46,7 @@ solution!(|path: PathBuf| {
 /// E.g., if there is a limit of 10 red cubes and the elf draws 12 red cubes in one game session,
 /// then we know it's "impossible" for there to be only 10 red cubes in the bag.
 #[derive(Debug, PartialEq)]
-struct Game {
+pub struct Game {
     id: i32,
     cube_draws: Vec<CubeDraw>,
 }
@@ -83,56 +89,67 @@ impl Game {
     }
 }
 
-impl From<String> for Game {
-    fn from(game_string: String) -> Game {
-        Game {
-            id: get_game_id(&game_string),
-            cube_draws: get_cube_draws(&game_string),
-        }
+impl TryFrom<&str> for Game {
+    type Error = anyhow::Error;
+
+    fn try_from(game_string: &str) -> Result<Game> {
+        Ok(Game {
+            id: get_game_id(game_string)?,
+            cube_draws: get_cube_draws(game_string)?,
+        })
     }
 }
 
 #[derive(Debug, PartialEq)]
+#[allow(clippy::struct_field_names)]
 struct CubeDraw {
     red_cubes: i32,
     green_cubes: i32,
     blue_cubes: i32,
 }
 
-impl From<&str> for CubeDraw {
-    fn from(game_string: &str) -> CubeDraw {
+impl TryFrom<&str> for CubeDraw {
+    type Error = anyhow::Error;
+
+    fn try_from(game_string: &str) -> Result<CubeDraw> {
         let mut red_cubes = 0;
         let mut green_cubes = 0;
         let mut blue_cubes = 0;
 
-        game_string.split(", ").for_each(|color_count| {
+        for color_count in game_string.split(", ") {
             let mut split = color_count.split_whitespace();
-            let count = split.next().unwrap_or("0").parse::<i32>().unwrap();
-            let color = split.next().unwrap_or("");
-            match color {
-                "red" => red_cubes += count,
-                "green" => green_cubes += count,
-                "blue" => blue_cubes += count,
-                _ => (),
-            };
-        });
+            let count = split.next().unwrap_or("0");
+            let count = count
+                .parse::<i32>()
+                .with_context(|| format!("invalid cube count '{count}'"))?;
+            match split.next() {
+                Some("red") => red_cubes += count,
+                Some("green") => green_cubes += count,
+                Some("blue") => blue_cubes += count,
+                Some(color) => bail!("unknown cube color '{color}'"),
+                None => bail!("the cube count {count} has no color"),
+            }
+        }
 
-        CubeDraw {
+        Ok(CubeDraw {
             red_cubes,
             green_cubes,
             blue_cubes,
-        }
+        })
     }
 }
 
 /// Given a string slice, return the first number encountered.
-fn get_game_id(line: &str) -> i32 {
-    line.chars()
+fn get_game_id(line: &str) -> Result<i32> {
+    let id = line
+        .chars()
         .skip_while(|ch| !ch.is_ascii_digit())
         .take_while(char::is_ascii_digit)
-        .collect::<String>()
-        .parse()
-        .unwrap_or(0)
+        .collect::<String>();
+    if id.is_empty() {
+        bail!("the game has no ID");
+    }
+    Ok(id.parse()?)
 }
 
 /// Parse a line of text and return a vec of the `CubeDraws` in that line.
@@ -141,12 +158,12 @@ fn get_game_id(line: &str) -> i32 {
 /// ```
 /// "Game 63: 4 red, 6 blue, 2 green; 3 green, 1 red, 5 blue; 7 blue, 5 green"
 /// ```
-fn get_cube_draws(line: &str) -> Vec<CubeDraw> {
-    let cube_draws = line.split(':').last().unwrap_or("");
+fn get_cube_draws(line: &str) -> Result<Vec<CubeDraw>> {
+    let cube_draws = line.split(':').next_back().unwrap_or("");
     if cube_draws.is_empty() {
-        return vec![];
+        return Ok(vec![]);
     }
-    cube_draws.split(';').map(CubeDraw::from).collect()
+    cube_draws.split(';').map(CubeDraw::try_from).collect()
 }
 
 #[cfg(test)]
@@ -156,7 +173,7 @@ mod tests {
     #[test]
     fn cube_draw_from_string() {
         assert_eq!(
-            CubeDraw::from("2 red, 1 blue, 4 green"),
+            CubeDraw::try_from("2 red, 1 blue, 4 green").unwrap(),
             CubeDraw {
                 red_cubes: 2,
                 blue_cubes: 1,
@@ -164,7 +181,7 @@ mod tests {
             }
         );
         assert_eq!(
-            CubeDraw::from("6 blue, 2 green"),
+            CubeDraw::try_from("6 blue, 2 green").unwrap(),
             CubeDraw {
                 red_cubes: 0,
                 blue_cubes: 6,
@@ -172,7 +189,7 @@ mod tests {
             }
         );
         assert_eq!(
-            CubeDraw::from("2 red, 5 green"),
+            CubeDraw::try_from("2 red, 5 green").unwrap(),
             CubeDraw {
                 red_cubes: 2,
                 blue_cubes: 0,
@@ -184,36 +201,41 @@ mod tests {
     #[test]
     fn get_game_id() {
         assert_eq!(
-            super::get_game_id("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
+            super::get_game_id("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap(),
             1
         );
         assert_eq!(
-            super::get_game_id("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"),
+            super::get_game_id("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue")
+                .unwrap(),
             2
         );
         assert_eq!(
-            super::get_game_id("Game 21: 11 blue, 9 red; 8 red, 2 blue; 2 red, 11 blue, 2 green"),
+            super::get_game_id("Game 21: 11 blue, 9 red; 8 red, 2 blue; 2 red, 11 blue, 2 green")
+                .unwrap(),
             21
         );
         assert_eq!(
-            super::get_game_id("Game 39: 10 blue, 4 green; 1 blue, 7 green, 5 red; 8 red, 2 blue",),
+            super::get_game_id("Game 39: 10 blue, 4 green; 1 blue, 7 green, 5 red; 8 red, 2 blue",)
+                .unwrap(),
             39
         );
         assert_eq!(
             super::get_game_id(
                 "Game 100: 10 blue, 2 red; 7 green, 20 blue, 9 red; 8 red, 6 green, 2 blue"
-            ),
+            )
+            .unwrap(),
             100
         );
     }
 
     #[test]
     fn get_cube_draws() {
-        assert!(super::get_cube_draws("").is_empty());
+        assert!(super::get_cube_draws("").unwrap().is_empty());
         assert_eq!(
             super::get_cube_draws(
                 "4 green, 9 blue, 2 red; 2 blue, 8 green; 2 green, 2 red, 6 blue"
-            ),
+            )
+            .unwrap(),
             vec![
                 CubeDraw {
                     red_cubes: 2,
@@ -234,6 +256,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn invalid_games() {
+        assert!(Game::try_from("Game one: blue").is_err());
+        assert!(Game::try_from("Game 7: three blue").is_err());
+        let error = Game::try_from("Game 1: 3 purple; 2 rde").unwrap_err();
+        assert_eq!(error.to_string(), "unknown cube color 'purple'");
+        let error = Game::try_from("Game 2: 3 red, 4").unwrap_err();
+        assert_eq!(error.to_string(), "the cube count 4 has no color");
+    }
+
     #[test]
     fn game_is_possible() {
         let possible_games = [
@@ -245,14 +277,14 @@ mod tests {
             "Game 6: 12 red, 14 blue, 13 green; 2 blue, 1 red, 2 green",
         ];
         for input in possible_games {
-            assert!(Game::from(input.to_string()).is_possible());
+            assert!(Game::try_from(input).unwrap().is_possible());
         }
         let impossible_games = [
             "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
             "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
         ];
         for input in impossible_games {
-            assert!(!Game::from(input.to_string()).is_possible());
+            assert!(!Game::try_from(input).unwrap().is_possible());
         }
     }
 
@@ -275,7 +307,7 @@ mod tests {
             ("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", 36),
         ] {
             assert_eq!(
-                Game::from((*input).to_string()).power(),
+                Game::try_from(*input).unwrap().power(),
                 expected_power.to_owned()
             );
         }
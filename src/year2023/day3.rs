@@ -1,155 +1,197 @@
-use regex::{Match, Regex};
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::path::PathBuf;
-
-use crate::io::LineExtractor;
-use crate::solution;
-
-const NON_SYMBOLS: &str = "01234566789.";
-
-solution!(|path| {
-    let content = File::open(path)
-        .expect("Cannot open file")
-        .lines()
-        .map(Result::unwrap)
-        .collect::<Vec<String>>();
-
-    let symbol_tracker = symbol_tracker(&content);
-    let part_number_sum = &symbol_tracker
-        .values()
-        .map(|numbers| numbers.iter().sum::<i32>())
-        .sum::<i32>();
-    let power_of_parts_touching_gears = &symbol_tracker
-        .values()
-        .filter(|parts| parts.len() == 2)
-        .filter_map(|parts| {
-            if parts.len() == 2 {
-                Some(parts.iter().product::<i32>())
-            } else {
-                None
-            }
-        })
-        .sum::<i32>();
-    println!("Sum of part numbers touching symbols: {part_number_sum}");
-    println!("Power of parts touching gears: {power_of_parts_touching_gears}");
-});
-
-#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-fn symbol_tracker(content: &[String]) -> HashMap<(i32, i32), Vec<i32>> {
-    let board = create_board(content);
-    let symbol_positions = find_symbols(&board);
-    let mut symbols_next_to_parts = HashMap::new();
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+
+use crate::grid::Grid;
+use crate::point::Point;
+use crate::solver::{Answer, Solver};
+
+const NON_SYMBOLS: &str = "0123456789.";
+
+#[derive(Default)]
+pub struct Solution;
 
-    for (row_index, row) in content.iter().enumerate() {
-        for number in extract_numbers(row) {
-            let edge_of_number = edge_of_number(row_index as i32, number);
-            for coords in &symbol_positions {
-                if edge_of_number.contains(coords) {
-                    let int = number.as_str().parse::<i32>().unwrap();
-                    symbols_next_to_parts
-                        .entry(*coords)
-                        .or_insert(Vec::new())
-                        .push(int);
+impl Solver for Solution {
+    /// The engine schematic.
+    type Parsed = Grid<char>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Grid::parse(input)
+    }
+
+    fn part1(&self, board: &Self::Parsed) -> Result<Answer> {
+        let part_number_sum = symbol_tracker(board)?
+            .values()
+            .map(|numbers| numbers.iter().sum::<i32>())
+            .sum::<i32>();
+        Ok(part_number_sum.into())
+    }
+
+    fn part2(&self, board: &Self::Parsed) -> Result<Answer> {
+        let power_of_parts_touching_gears = symbol_tracker(board)?
+            .values()
+            .filter_map(|parts| {
+                if parts.len() == 2 {
+                    Some(parts.iter().product::<i32>())
+                } else {
+                    None
                 }
-            }
-        }
+            })
+            .sum::<i32>();
+        Ok(power_of_parts_touching_gears.into())
     }
-    symbols_next_to_parts
 }
 
-#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-fn edge_of_number(row_index: i32, number: Match<'_>) -> HashSet<(i32, i32)> {
-    [row_index - 1, row_index, row_index + 1]
-        .iter()
-        .flat_map(|y| {
-            let start = number.start() as i32 - 1;
-            let end = number.end() as i32 + 1;
-            (start..end).filter_map(move |x| if x < 0 || *y < 0 { None } else { Some((x, *y)) })
-        })
-        .collect()
+/// A number on the engine schematic, which is always written left to right on one row.
+#[derive(Debug, PartialEq)]
+struct PartNumber {
+    value: i32,
+    /// The position of the first digit.
+    start: Point,
+    /// The position of the last digit.
+    end: Point,
 }
 
-fn create_board(lines: &[String]) -> Vec<Vec<char>> {
-    lines
-        .iter()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect()
+impl PartNumber {
+    /// The cells around the number, plus the number itself.
+    fn surroundings<'a>(&self, board: &'a Grid<char>) -> impl Iterator<Item = (Point, &'a char)> {
+        board.region(self.start + Point::new(-1, -1), self.end + Point::new(1, 1))
+    }
 }
 
-#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-fn find_symbols(board: &[Vec<char>]) -> HashSet<(i32, i32)> {
-    board
-        .iter()
-        .enumerate()
-        .flat_map(|(x, row)| {
-            row.iter().enumerate().filter_map(move |(y, ch)| {
-                if NON_SYMBOLS.contains(*ch) {
-                    None
-                } else {
-                    // We have to index the board by row then column,
-                    // so we invert x and y.
-                    Some((y as i32, x as i32))
-                }
-            })
-        })
-        .collect()
+/// Map the position of every symbol to the numbers touching it.
+fn symbol_tracker(board: &Grid<char>) -> Result<HashMap<Point, Vec<i32>>> {
+    let mut symbols_next_to_parts = HashMap::new();
+    for number in extract_numbers(board)? {
+        for (position, _) in number.surroundings(board).filter(|(_, c)| is_symbol(**c)) {
+            symbols_next_to_parts
+                .entry(position)
+                .or_insert(Vec::new())
+                .push(number.value);
+        }
+    }
+    Ok(symbols_next_to_parts)
 }
 
-fn extract_numbers(line: &str) -> Vec<Match> {
-    Regex::new(r"\d+").unwrap().find_iter(line).collect()
+fn is_symbol(c: char) -> bool {
+    !NON_SYMBOLS.contains(c)
+}
+
+/// Find every run of digits on the board.
+fn extract_numbers(board: &Grid<char>) -> Result<Vec<PartNumber>> {
+    let mut numbers = Vec::new();
+    for (row, cells) in board.rows().enumerate() {
+        let mut column = 0;
+        while column < cells.len() {
+            let length = cells[column..]
+                .iter()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            if length == 0 {
+                column += 1;
+                continue;
+            }
+            let digits: String = cells[column..column + length].iter().collect();
+            let value = digits
+                .parse()
+                .with_context(|| format!("line {}: invalid part number '{digits}'", row + 1))?;
+            numbers.push(PartNumber {
+                value,
+                start: Point::from_indices(column, row),
+                end: Point::from_indices(column + length - 1, row),
+            });
+            column += length;
+        }
+    }
+    Ok(numbers)
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
-    fn get_content() -> Vec<String> {
-        vec![
-            "467..114..".to_owned(),
-            "...*......".to_owned(),
-            "..35..633.".to_owned(),
-            "......#...".to_owned(),
-            "617*......".to_owned(),
-            ".....+.58.".to_owned(),
-            "..592.....".to_owned(),
-            "......755.".to_owned(),
-            "...$.*....".to_owned(),
-            ".664.598..".to_owned(),
-        ]
+    use super::PartNumber;
+    use crate::grid::Grid;
+    use crate::point::Point;
+
+    fn get_board() -> Grid<char> {
+        Grid::parse(
+            "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..",
+        )
+        .unwrap()
     }
 
     #[test]
     fn get_symbol_positions() {
-        let board = super::create_board(&get_content());
-        let expected = HashSet::from([(5, 5), (3, 8), (5, 8), (3, 1), (3, 4), (6, 3)]);
-        assert_eq!(super::find_symbols(&board), expected);
+        let symbol_tracker = super::symbol_tracker(&get_board()).unwrap();
+        let actual: HashSet<Point> = symbol_tracker.keys().copied().collect();
+        let expected = HashSet::from([
+            Point::new(5, 5),
+            Point::new(3, 8),
+            Point::new(5, 8),
+            Point::new(3, 1),
+            Point::new(3, 4),
+            Point::new(6, 3),
+        ]);
+        assert_eq!(actual, expected);
     }
 
     #[test]
     fn get_edges_around_number() {
-        let board = get_content();
-        let first_numbers = super::extract_numbers(&board[0]);
+        let board = get_board();
+        let numbers = super::extract_numbers(&board).unwrap();
+        assert_eq!(
+            numbers[0],
+            PartNumber {
+                value: 467,
+                start: Point::ORIGIN,
+                end: Point::new(2, 0),
+            }
+        );
         let expected_edge = HashSet::from([
-            (2, 1),
-            (1, 1),
-            (2, 0),
-            (1, 0),
-            (0, 1),
-            (3, 1),
-            (0, 0),
-            (3, 0),
+            Point::new(2, 1),
+            Point::new(1, 1),
+            Point::new(2, 0),
+            Point::new(1, 0),
+            Point::new(0, 1),
+            Point::new(3, 1),
+            Point::new(0, 0),
+            Point::new(3, 0),
         ]);
-        assert_eq!(super::edge_of_number(0, first_numbers[0]), expected_edge);
+        let actual_edge: HashSet<Point> = numbers[0]
+            .surroundings(&board)
+            .map(|(position, _)| position)
+            .collect();
+        assert_eq!(actual_edge, expected_edge);
     }
 
     #[test]
     fn total_engine_part_numbers() {
-        let content = get_content();
-        let part_number_sum = super::symbol_tracker(&content)
+        let part_number_sum = super::symbol_tracker(&get_board())
+            .unwrap()
             .values()
             .map(|numbers| numbers.iter().sum::<i32>())
             .sum::<i32>();
         assert_eq!(part_number_sum, 4361);
     }
+
+    #[test]
+    fn gear_ratios() {
+        let gear_ratio_sum = super::symbol_tracker(&get_board())
+            .unwrap()
+            .values()
+            .filter(|parts| parts.len() == 2)
+            .map(|parts| parts.iter().product::<i32>())
+            .sum::<i32>();
+        assert_eq!(gear_ratio_sum, 467_835);
+    }
 }
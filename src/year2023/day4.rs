@@ -1,36 +1,40 @@
-use std::fs::read_to_string;
-use std::path::PathBuf;
-
+use anyhow::Result;
 use nom::{
     bytes::complete::tag,
-    character::complete::{self, digit1, multispace0, multispace1, space1},
+    character::complete::multispace0,
     combinator::recognize,
-    multi::separated_list1,
-    sequence::{delimited, preceded, separated_pair, tuple},
+    sequence::{delimited, preceded, separated_pair},
     IResult,
 };
 
-use crate::solution;
-
-solution!(|path| {
-    let content = read_to_string(path)
-        .unwrap()
-        .lines()
-        .map(str::to_string)
-        .collect::<Vec<String>>();
-    let scratch_cards = ScratchCard::from_lines(&content);
-    println!(
-        "Sum of scratch card scores: {}",
-        scratch_cards.iter().map(ScratchCard::score).sum::<i32>(),
-    );
-    println!(
-        "Sum of scratch cards generated: {}",
-        multiply_cards(&scratch_cards)
-    );
-});
+use crate::parse;
+use crate::solver::{Answer, Solver};
+
+#[derive(Default)]
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Vec<ScratchCard>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        ScratchCard::from_lines(input)
+    }
+
+    fn part1(&self, scratch_cards: &Self::Parsed) -> Result<Answer> {
+        Ok(scratch_cards
+            .iter()
+            .map(ScratchCard::score)
+            .sum::<i32>()
+            .into())
+    }
+
+    fn part2(&self, scratch_cards: &Self::Parsed) -> Result<Answer> {
+        Ok(multiply_cards(scratch_cards).into())
+    }
+}
 
 #[derive(Debug, PartialEq)]
-struct ScratchCard {
+pub struct ScratchCard {
     winning: Vec<i32>,
     drawn: Vec<i32>,
 }
@@ -52,37 +56,37 @@ impl ScratchCard {
             .count()
     }
 
-    fn from_lines(lines: &[String]) -> Vec<Self> {
-        lines
-            .iter()
-            .map(|line| ScratchCard::from(line.as_str()))
-            .collect()
+    fn from_lines(input: &str) -> Result<Vec<Self>> {
+        parse::lines(input, |line| ScratchCard::try_from(line))
     }
 }
 
-impl From<&str> for ScratchCard {
+impl TryFrom<&str> for ScratchCard {
+    type Error = anyhow::Error;
+
     /// Parse strings like this:
     /// ```
     /// "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
     /// ```
     /// Winning numbers are on the left side of the `|`.
     /// Numbers on the scratch card are on the right side.
-    #[allow(clippy::match_wild_err_arm)]
-    fn from(line: &str) -> Self {
-        let (_, (winning, drawn)) =
-            parse_line(line).unwrap_or_else(|_| panic!("Should parse '{line}'"));
-        Self { winning, drawn }
+    fn try_from(line: &str) -> Result<Self> {
+        let (winning, drawn) = parse::parse_all(line, parse_line)?;
+        Ok(Self { winning, drawn })
     }
 }
 
 fn parse_line(input: &str) -> IResult<&str, (Vec<i32>, Vec<i32>)> {
-    separated_pair(preceded(card, number_sequence), number_sep, number_sequence)(input)
+    separated_pair(
+        preceded(card, parse::unsigned_list),
+        number_sep,
+        parse::unsigned_list,
+    )(input)
 }
 
 /// Parses a card prefix from a string. Whitespace after the `:` is greedily matched.
 fn card(input: &str) -> IResult<&str, &str> {
-    let char_seq = (tag("Card"), space1, digit1, tag(":"), space1);
-    recognize(tuple(char_seq))(input)
+    recognize(parse::header::<u32>("Card"))(input)
 }
 
 /// Parse the separator between the winning and drawn numbers: ` | `.
@@ -90,23 +94,16 @@ fn number_sep(input: &str) -> IResult<&str, &str> {
     delimited(multispace0, tag("|"), multispace0)(input)
 }
 
-/// Parse a sequence of one or more digits separated by spaces into a `Vec<i32>`.
-/// The input string must start with a number.
-fn number_sequence(input: &str) -> IResult<&str, Vec<i32>> {
-    separated_list1(multispace1, complete::i32)(input)
-}
-
 fn multiply_cards(scratch_cards: &[ScratchCard]) -> usize {
     let mut card_counter = vec![1; scratch_cards.len()];
-    let final_index = card_counter.len() - 1;
     for (index, card) in scratch_cards.iter().enumerate() {
         let total_wins = card.total_winning_numbers();
         if total_wins == 0 {
             continue;
         }
-        let start = (index + 1).min(final_index);
-        let end = (index + total_wins).min(final_index);
-        for update_index in start..=end {
+        // Cards are never won past the end of the table.
+        let end = (index + total_wins).min(scratch_cards.len() - 1);
+        for update_index in index + 1..=end {
             card_counter[update_index] += card_counter[index];
         }
     }
@@ -148,7 +145,7 @@ mod tests {
 
     #[test]
     fn scratch_card_parsing() {
-        let actual: Vec<ScratchCard> = ScratchCard::from_lines(&input_file());
+        let actual: Vec<ScratchCard> = ScratchCard::from_lines(&input_file().join("\n")).unwrap();
         let expected = vec![
             ScratchCard {
                 winning: vec![41, 48, 83, 86, 17],
@@ -178,9 +175,20 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn invalid_cards() {
+        let error =
+            ScratchCard::from_lines("Card 1: 41 48 | 83 86\nCard 2: 13 32 / 61 30").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "line 2: 'Card 2: 13 32 / 61 30': column 15: expected Tag, found '/ 61 30'"
+        );
+    }
+
     #[test]
     fn scoring() {
-        let scores: Vec<i32> = ScratchCard::from_lines(&input_file())
+        let scores: Vec<i32> = ScratchCard::from_lines(&input_file().join("\n"))
+            .unwrap()
             .iter()
             .map(ScratchCard::score)
             .collect();
@@ -195,7 +203,8 @@ mod tests {
 
     #[test]
     fn counting() {
-        let count: Vec<usize> = ScratchCard::from_lines(&input_file())
+        let count: Vec<usize> = ScratchCard::from_lines(&input_file().join("\n"))
+            .unwrap()
             .iter()
             .map(ScratchCard::total_winning_numbers)
             .collect();
@@ -209,8 +218,16 @@ mod tests {
 
     #[test]
     fn card_totaling() {
-        let cards = ScratchCard::from_lines(&input_file());
+        let cards = ScratchCard::from_lines(&input_file().join("\n")).unwrap();
         let actual = super::multiply_cards(&cards);
         assert_eq!(actual, 30);
     }
+
+    #[test]
+    fn card_totaling_at_the_edges() {
+        assert_eq!(super::multiply_cards(&[]), 0);
+        // The last card wins, but there are no cards after it to copy.
+        let cards = ScratchCard::from_lines("Card 1: 1 2 | 3 4\nCard 2: 5 | 5").unwrap();
+        assert_eq!(super::multiply_cards(&cards), 2);
+    }
 }
@@ -1,55 +1,109 @@
 // I got super stuck, so I went to reddit and found https://github.com/rvodden/AoC23/blob/main/day-05/src/part1.rs.
 // rvodden is very clever and helped me a lot. Now I know `nom` is the perfect library for AoC!
-use std::{fs::read_to_string, ops::Range, path::PathBuf};
+use std::ops::Range;
 
-use crate::solution;
+use anyhow::{bail, Context, Result};
+
+use crate::interval::IntervalSet;
+use crate::parse;
+use crate::solver::{Answer, Solver};
+use crate::trace::{debug, trace};
 
 use nom::{
     bytes::complete::tag,
-    character::complete::{self, alpha1, newline, space1},
-    multi::{many1, separated_list1},
-    sequence::{pair, preceded, separated_pair, terminated, tuple},
+    character::complete::{alpha1, line_ending, space1},
+    error::{Error, ErrorKind},
+    multi::separated_list1,
+    sequence::{pair, separated_pair, terminated, tuple},
     IResult,
 };
 
-solution!(|path| {
-    let content = read_to_string(path).expect("Should read file");
-    let (seeds, almanac_ranges) = parse_file(&content);
-    let min_location = seeds
-        .into_iter()
-        .map(|seed_num| follow_map(seed_num, "seed", &almanac_ranges))
-        .min()
-        .unwrap();
-    println!("Minimum location: {min_location}");
-});
-
-fn parse_file(content: &str) -> (Vec<u64>, Vec<AlmanacRange>) {
-    let mut parser = separated_pair(
-        seeds,
-        pair(newline, newline),
-        separated_list1(newline, AlmanacRange::complete_mapping),
-    );
-    let (_, (seeds, ranges)) = parser(content).expect("Should parse file");
-    (seeds, ranges)
+#[derive(Default)]
+pub struct Solution;
+
+impl Solver for Solution {
+    /// The numbers on the seeds line, and the maps of the almanac.
+    type Parsed = (Vec<u64>, Vec<AlmanacRange>);
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        parse_file(input)
+    }
+
+    fn part1(&self, (seeds, almanac_ranges): &Self::Parsed) -> Result<Answer> {
+        let min_location = seeds
+            .iter()
+            .map(|&seed_num| {
+                let location = follow_map(seed_num, "seed", almanac_ranges);
+                debug!("seed {seed_num} is planted at location {location}");
+                location
+            })
+            .min()
+            .context("the almanac lists no seeds")?;
+        Ok(min_location.into())
+    }
+
+    fn part2(&self, (seeds, almanac_ranges): &Self::Parsed) -> Result<Answer> {
+        let min_location = follow_map_set(&seed_ranges(seeds)?, "seed", almanac_ranges)
+            .min()
+            .context("the almanac lists no seeds")?;
+        Ok(min_location.into())
+    }
+}
+
+/// Read the seeds line as pairs of a start and a length.
+fn seed_ranges(seeds: &[u64]) -> Result<IntervalSet<u64>> {
+    if !seeds.len().is_multiple_of(2) {
+        bail!("the seeds should come in pairs of a start and a length");
+    }
+    seeds
+        .chunks(2)
+        .map(|pair| {
+            let (start, length) = (pair[0], pair[1]);
+            let end = start
+                .checked_add(length)
+                .with_context(|| format!("the seed range '{start} {length}' is too large"))?;
+            Ok(start..end)
+        })
+        .collect()
+}
+
+fn parse_file(content: &str) -> Result<(Vec<u64>, Vec<AlmanacRange>)> {
+    parse::parse_all(
+        content,
+        separated_pair(
+            parse::labelled("seeds", parse::unsigned_list),
+            parse::blank_line,
+            parse::blocks(AlmanacRange::complete_mapping),
+        ),
+    )
 }
 
 fn follow_map(value: u64, destination: &str, almanac_ranges: &[AlmanacRange]) -> u64 {
-    print!("{destination} {value:10} ");
+    trace!("{destination} {value}");
     if let Some(almanac_range) = almanac_ranges.iter().find(|ar| ar.source == destination) {
         let value = almanac_range.resolve(value);
         follow_map(value, &almanac_range.destination, almanac_ranges)
     } else {
-        println!();
         value
     }
 }
 
-fn seeds(line: &str) -> IResult<&str, Vec<u64>> {
-    preceded(tag("seeds: "), separated_list1(space1, complete::u64))(line)
+/// Like [`follow_map`], but for every value in a set at once.
+fn follow_map_set(
+    values: &IntervalSet<u64>,
+    destination: &str,
+    almanac_ranges: &[AlmanacRange],
+) -> IntervalSet<u64> {
+    if let Some(almanac_range) = almanac_ranges.iter().find(|ar| ar.source == destination) {
+        let values = almanac_range.resolve_set(values);
+        follow_map_set(&values, &almanac_range.destination, almanac_ranges)
+    } else {
+        values.clone()
+    }
 }
 
 #[derive(Clone)]
-struct AlmanacRange {
+pub struct AlmanacRange {
     source: String,
     destination: String,
     ranges: Vec<(Range<u64>, u64)>,
@@ -62,14 +116,26 @@ impl AlmanacRange {
     fn resolve(&self, source: u64) -> u64 {
         for (range, destination_start) in &self.ranges {
             if range.contains(&source) {
-                let source_start = range.clone().min().expect("Should have minimum");
-                let offset_from_start = source - source_start;
+                let offset_from_start = source - range.start;
                 return destination_start + offset_from_start;
             }
         }
         source
     }
 
+    /// Resolve every value in a set. Values in a mapped range are moved to the destination of
+    /// that range, and the rest are kept as-is.
+    fn resolve_set(&self, sources: &IntervalSet<u64>) -> IntervalSet<u64> {
+        let mapped_sources: IntervalSet<u64> =
+            self.ranges.iter().map(|(range, _)| range.clone()).collect();
+        self.ranges
+            .iter()
+            .map(|(range, destination_start)| sources.map_range(range.clone(), *destination_start))
+            .fold(sources.difference(&mapped_sources), |resolved, moved| {
+                resolved.union(&moved)
+            })
+    }
+
     /// Parses a complete mapping of numbers for values of the given types.
     /// E.g.,
     /// ```
@@ -78,8 +144,11 @@ impl AlmanacRange {
     /// 52 50 48"
     /// ```
     fn complete_mapping(input: &str) -> IResult<&str, AlmanacRange> {
-        let (input, ((source, destination), ranges)) =
-            separated_pair(Self::header, newline, many1(Self::range_mapping))(input)?;
+        let (input, ((source, destination), ranges)) = separated_pair(
+            Self::header,
+            line_ending,
+            separated_list1(line_ending, Self::range_mapping),
+        )(input)?;
 
         Ok((
             input,
@@ -102,13 +171,100 @@ impl AlmanacRange {
 
     /// Parses one line of a range map.
     /// E.g., `"1965922922 2387203602 59808406"`
+    /// Fails without backtracking if either range runs past the largest `u64`.
     fn range_mapping(line: &str) -> IResult<&str, (Range<u64>, u64)> {
-        let (line, (dest_start, source_start, length)): (&str, (u64, u64, u64)) = tuple((
-            terminated(complete::u64, space1),
-            terminated(complete::u64, space1),
-            terminated(complete::u64, newline),
+        let (rest, (dest_start, source_start, length)): (&str, (u64, u64, u64)) = tuple((
+            terminated(parse::unsigned, space1),
+            terminated(parse::unsigned, space1),
+            parse::unsigned,
         ))(line)?;
 
-        Ok((line, (source_start..(source_start + length), dest_start)))
+        match (
+            source_start.checked_add(length),
+            dest_start.checked_add(length),
+        ) {
+            (Some(source_end), Some(_)) => Ok((rest, (source_start..source_end, dest_start))),
+            _ => Err(nom::Err::Failure(Error::new(line, ErrorKind::TooLarge))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn lowest_location() {
+        let almanac = Solution.parse(EXAMPLE).unwrap();
+        assert_eq!(Solution.part1(&almanac).unwrap().to_string(), "35");
+        assert_eq!(Solution.part2(&almanac).unwrap().to_string(), "46");
+    }
+
+    #[test]
+    fn resolving_sets_matches_single_values() {
+        let (_, almanac_ranges) = parse_file(EXAMPLE).unwrap();
+        for almanac_range in &almanac_ranges {
+            let resolved = almanac_range.resolve_set(&IntervalSet::from(0..100));
+            let mut expected: Vec<u64> = (0..100).map(|v| almanac_range.resolve(v)).collect();
+            expected.sort_unstable();
+            assert_eq!(resolved.ranges().flatten().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn odd_seed_count() {
+        assert!(seed_ranges(&[79, 14, 55]).is_err());
+    }
+
+    #[test]
+    fn ranges_past_the_largest_number() {
+        let error = seed_ranges(&[79, 14, u64::MAX, 5]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the seed range '18446744073709551615 5' is too large"
+        );
+        let error =
+            parse_file("seeds: 1 2\n\nseed-to-soil map:\n50 98 2\n0 18446744073709551615 5")
+                .err()
+                .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 5, column 1: expected TooLarge, found '0 18446744073709551615 5'"
+        );
     }
 }
@@ -1,75 +1,85 @@
-use std::{fs::read_to_string, path::PathBuf};
-
+use anyhow::{bail, Result};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{self, digit1, multispace1, newline},
-    combinator::recognize,
+    character::complete::{digit1, line_ending, space1},
+    combinator::map_res,
     multi::separated_list1,
-    sequence::{preceded, separated_pair, tuple},
+    sequence::separated_pair,
     IResult,
 };
 
-use crate::solution;
+use crate::math;
+use crate::parse;
+use crate::solver::{Answer, Solver};
 
-solution!(|path| {
-    let content = read_to_string(path).expect("Should read file");
-    println!(
-        "Product of winning times: {}",
-        parse_file(&content)
-            .iter()
-            .map(TimedRace::count_better_times)
-            .product::<u64>(),
-    );
-
-    println!(
-        "Number of winning times (part 2): {}",
-        parse_file_p2(&content).count_better_times(),
-    );
-});
-
-fn parse_file(content: &str) -> Vec<TimedRace> {
-    let (_, (times, distances)) =
-        separated_pair(times, newline, distances)(content).expect("Should be able to parse file");
-    times
-        .iter()
-        .zip(&distances)
-        .map(|(time, distance)| TimedRace::new(*time, *distance))
-        .collect()
-}
+#[derive(Default)]
+pub struct Solution;
 
-fn times(input: &str) -> IResult<&str, Vec<u64>> {
-    let prefix = recognize(tuple((tag("Time:"), multispace1)));
-    preceded(prefix, separated_list1(multispace1, complete::u64))(input)
-}
+impl Solver for Solution {
+    /// The races as written, and the single long race that part 2 reads once the spaces between
+    /// the digits are ignored.
+    type Parsed = (Vec<TimedRace>, TimedRace);
 
-fn distances(input: &str) -> IResult<&str, Vec<u64>> {
-    let prefix = recognize(tuple((tag("Distance:"), multispace1)));
-    preceded(prefix, separated_list1(multispace1, complete::u64))(input)
-}
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok((parse_file(input)?, parse_file_p2(input)?))
+    }
+
+    fn part1(&self, (races, _): &Self::Parsed) -> Result<Answer> {
+        Ok(races
+            .iter()
+            .map(TimedRace::count_better_times)
+            .product::<u64>()
+            .into())
+    }
 
-fn parse_file_p2(content: &str) -> TimedRace {
-    let (_, (time, distance)) =
-        separated_pair(time, newline, distance)(content).expect("Should parse file (pt 2)");
-    TimedRace { time, distance }
+    fn part2(&self, (_, long_race): &Self::Parsed) -> Result<Answer> {
+        Ok(long_race.count_better_times().into())
+    }
 }
 
-fn time(input: &str) -> IResult<&str, u64> {
-    let prefix = recognize(tuple((tag("Time:"), multispace1)));
-    preceded(prefix, fold_number)(input)
+fn parse_file(content: &str) -> Result<Vec<TimedRace>> {
+    let (times, distances): (Vec<u64>, Vec<u64>) = parse::parse_all(
+        content,
+        separated_pair(
+            parse::labelled("Time", parse::unsigned_list),
+            line_ending,
+            parse::labelled("Distance", parse::unsigned_list),
+        ),
+    )?;
+    if times.len() != distances.len() {
+        bail!(
+            "there are {} times but {} distances",
+            times.len(),
+            distances.len()
+        );
+    }
+    Ok(times
+        .iter()
+        .zip(&distances)
+        .map(|(time, distance)| TimedRace::new(*time, *distance))
+        .collect())
 }
 
-fn distance(input: &str) -> IResult<&str, u64> {
-    let prefix = recognize(tuple((tag("Distance:"), multispace1)));
-    preceded(prefix, fold_number)(input)
+fn parse_file_p2(content: &str) -> Result<TimedRace> {
+    let (time, distance) = parse::parse_all(
+        content,
+        separated_pair(
+            parse::labelled("Time", fold_number),
+            line_ending,
+            parse::labelled("Distance", fold_number),
+        ),
+    )?;
+    Ok(TimedRace { time, distance })
 }
 
+/// Parse numbers separated by spaces as one number, ignoring the spaces.
 fn fold_number(input: &str) -> IResult<&str, u64> {
-    let (input, folded_num) = separated_list1(multispace1, digit1)(input)?;
-    Ok((input, folded_num.join("").parse().expect("Parsed number")))
+    map_res(separated_list1(space1, digit1), |digits: Vec<&str>| {
+        digits.concat().parse()
+    })(input)
 }
 
 #[derive(Debug, PartialEq)]
-struct TimedRace {
+pub struct TimedRace {
     time: u64,
     distance: u64,
 }
@@ -79,20 +89,11 @@ impl TimedRace {
         Self { time, distance }
     }
 
+    /// Count the button hold times that go further than the record. Holding the button for `x`
+    /// milliseconds travels `x * (time - x)`, so this counts the integers between the roots.
     fn count_better_times(&self) -> u64 {
-        (1..self.time)
-            .filter_map(|button_held_duraction| {
-                let race_time_left = self.time - button_held_duraction;
-                let distance = button_held_duraction * race_time_left;
-                if distance > self.distance {
-                    Some(distance)
-                } else {
-                    None
-                }
-            })
-            .count()
-            .try_into()
-            .unwrap_or_else(|_| panic!("Failed to get race times for {:?}", &self))
+        math::between_roots(self.time, self.distance)
+            .map_or(0, |held| held.end() - held.start() + 1)
     }
 }
 
@@ -109,7 +110,7 @@ mod tests {
 
     #[test]
     fn parsing() {
-        let actual = parse_file(&input_file());
+        let actual = parse_file(&input_file()).unwrap();
         let expected = vec![
             TimedRace {
                 time: 7,
@@ -154,4 +155,26 @@ mod tests {
             9
         );
     }
+
+    #[test]
+    fn counting_matches_every_hold_time() {
+        for time in 0..60 {
+            for distance in 0..=time * time / 4 + 1 {
+                let brute_force = (1..time)
+                    .filter(|held| held * (time - held) > distance)
+                    .count() as u64;
+                assert_eq!(
+                    TimedRace::new(time, distance).count_better_times(),
+                    brute_force,
+                    "time {time}, distance {distance}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn long_race() {
+        let race = parse_file_p2(&input_file()).unwrap();
+        assert_eq!(race.count_better_times(), 71503);
+    }
 }
@@ -0,0 +1,222 @@
+use anyhow::Result;
+use nom::{
+    character::complete::{anychar, space1},
+    combinator::{map_opt, map_res},
+    multi::count,
+    sequence::separated_pair,
+    IResult,
+};
+
+use crate::parse;
+use crate::solver::{Answer, Solver};
+
+#[derive(Default)]
+pub struct Solution;
+
+impl Solver for Solution {
+    /// The hands, reading each `J` as a jack.
+    type Parsed = Vec<Hand>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Hand::from_lines(input)
+    }
+
+    fn part1(&self, hands: &Self::Parsed) -> Result<Answer> {
+        Ok(total_winnings(hands.clone()).into())
+    }
+
+    fn part2(&self, hands: &Self::Parsed) -> Result<Answer> {
+        Ok(total_winnings(hands.iter().map(Hand::jokers_wild).collect()).into())
+    }
+}
+
+/// A card, from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Card {
+    Joker,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Card {
+    fn parse(c: char) -> Option<Self> {
+        Some(match c {
+            '2' => Card::Two,
+            '3' => Card::Three,
+            '4' => Card::Four,
+            '5' => Card::Five,
+            '6' => Card::Six,
+            '7' => Card::Seven,
+            '8' => Card::Eight,
+            '9' => Card::Nine,
+            'T' => Card::Ten,
+            'J' => Card::Jack,
+            'Q' => Card::Queen,
+            'K' => Card::King,
+            'A' => Card::Ace,
+            _ => return None,
+        })
+    }
+}
+
+/// The type of a hand, from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum HandKind {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandKind {
+    /// Classify some cards. Jokers join the largest group of matching cards, which always makes
+    /// the strongest hand.
+    fn of(cards: [Card; 5]) -> Self {
+        let mut counts = [0u8; 14];
+        for card in cards {
+            counts[card as usize] += 1;
+        }
+        let jokers = std::mem::take(&mut counts[Card::Joker as usize]);
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        match (counts[0] + jokers, counts[1]) {
+            (5, _) => HandKind::FiveOfAKind,
+            (4, _) => HandKind::FourOfAKind,
+            (3, 2) => HandKind::FullHouse,
+            (3, _) => HandKind::ThreeOfAKind,
+            (2, 2) => HandKind::TwoPair,
+            (2, _) => HandKind::OnePair,
+            _ => HandKind::HighCard,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hand {
+    cards: [Card; 5],
+    bid: u64,
+}
+
+impl Hand {
+    /// Hands are ranked by their kind, and then card by card from the first.
+    fn strength(&self) -> (HandKind, [Card; 5]) {
+        (HandKind::of(self.cards), self.cards)
+    }
+
+    /// The same hand where every jack is a joker instead, which can stand in for any card but is
+    /// the weakest card.
+    fn jokers_wild(&self) -> Self {
+        let cards = self.cards.map(|card| match card {
+            Card::Jack => Card::Joker,
+            card => card,
+        });
+        Self { cards, ..*self }
+    }
+
+    fn from_lines(input: &str) -> Result<Vec<Self>> {
+        parse::lines(input, |line| parse::parse_all(line, hand))
+    }
+}
+
+/// Parse a hand and its bid, like `32T3K 765`.
+fn hand(input: &str) -> IResult<&str, Hand> {
+    let cards = map_res(
+        count(map_opt(anychar, Card::parse), 5),
+        <[Card; 5]>::try_from,
+    );
+    let (input, (cards, bid)) = separated_pair(cards, space1, parse::unsigned)(input)?;
+    Ok((input, Hand { cards, bid }))
+}
+
+/// Each hand wins its bid multiplied by its rank, where the weakest hand has rank 1.
+fn total_winnings(mut hands: Vec<Hand>) -> u64 {
+    hands.sort_by_cached_key(Hand::strength);
+    hands
+        .iter()
+        .zip(1..)
+        .map(|(hand, rank)| hand.bid * rank)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    fn hand(text: &str) -> Hand {
+        Hand::from_lines(&format!("{text} 0")).unwrap().remove(0)
+    }
+
+    #[test]
+    fn parsing() {
+        let hands = Hand::from_lines(EXAMPLE).unwrap();
+        assert_eq!(
+            hands[0],
+            Hand {
+                cards: [Card::Three, Card::Two, Card::Ten, Card::Three, Card::King],
+                bid: 765,
+            }
+        );
+        assert_eq!(hand("JJ2AQ").cards[..2], [Card::Jack; 2]);
+        assert_eq!(hand("JJ2AQ").jokers_wild().cards[..2], [Card::Joker; 2]);
+        let error = Hand::from_lines("32T3K 765\n32X3K 1").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "line 2: '32X3K 1': column 3: expected MapOpt, found 'X3K 1'"
+        );
+        assert!(Hand::from_lines("32T3KK 765").is_err());
+    }
+
+    #[test]
+    fn hand_kinds() {
+        let kind = |text| HandKind::of(hand(text).cards);
+        assert_eq!(kind("AAAAA"), HandKind::FiveOfAKind);
+        assert_eq!(kind("AA8AA"), HandKind::FourOfAKind);
+        assert_eq!(kind("23332"), HandKind::FullHouse);
+        assert_eq!(kind("TTT98"), HandKind::ThreeOfAKind);
+        assert_eq!(kind("23432"), HandKind::TwoPair);
+        assert_eq!(kind("A23A4"), HandKind::OnePair);
+        assert_eq!(kind("23456"), HandKind::HighCard);
+
+        let wild_kind = |text| HandKind::of(hand(text).jokers_wild().cards);
+        assert_eq!(kind("QJJQ2"), HandKind::TwoPair);
+        assert_eq!(wild_kind("QJJQ2"), HandKind::FourOfAKind);
+        assert_eq!(wild_kind("JJJJJ"), HandKind::FiveOfAKind);
+        assert_eq!(wild_kind("2233J"), HandKind::FullHouse);
+        assert_eq!(wild_kind("2345J"), HandKind::OnePair);
+    }
+
+    #[test]
+    fn ordering_by_cards() {
+        let strength = |text| hand(text).jokers_wild().strength();
+        assert!(strength("33332") > strength("2AAAA"));
+        assert!(strength("77888") > strength("77788"));
+        // A joker counts as a four of a kind, but it is weaker than any other card.
+        assert!(strength("JKKK2") < strength("QQQQ2"));
+    }
+
+    #[test]
+    fn winnings() {
+        let hands = Solution.parse(EXAMPLE).unwrap();
+        assert_eq!(Solution.part1(&hands).unwrap().to_string(), "6440");
+        assert_eq!(Solution.part2(&hands).unwrap().to_string(), "5905");
+    }
+}
@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, line_ending, one_of},
+    combinator::map,
+    multi::{many1, separated_list1},
+    sequence::{delimited, separated_pair, tuple},
+    IResult,
+};
+
+use crate::math;
+use crate::parse;
+use crate::solver::{Answer, Solver};
+
+#[derive(Default)]
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Map::parse(input)
+    }
+
+    fn part1(&self, map: &Self::Parsed) -> Result<Answer> {
+        let start = map.node("AAA")?;
+        let end = map.node("ZZZ")?;
+        Ok(map.steps_until(start, |node| node == end)?.into())
+    }
+
+    fn part2(&self, map: &Self::Parsed) -> Result<Answer> {
+        let ghosts: Vec<Ghost> = map
+            .nodes_ending_with('A')
+            .map(|start| map.ghost(start))
+            .collect();
+        if ghosts.is_empty() {
+            bail!("there are no nodes ending in A to start from");
+        }
+        Ok(steps_until_all_arrive(&ghosts)
+            .context("the ghosts never reach nodes ending in Z at the same time")?
+            .into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Left,
+    Right,
+}
+
+/// The instructions and the network of nodes, with each node name replaced by an index.
+#[derive(Debug)]
+pub struct Map {
+    instructions: Vec<Instruction>,
+    names: Vec<String>,
+    /// The left and right neighbours of each node.
+    network: Vec<(usize, usize)>,
+}
+
+impl Map {
+    fn parse(input: &str) -> Result<Self> {
+        let (instructions, nodes) = parse::parse_all(
+            input,
+            separated_pair(
+                instructions,
+                parse::blank_line,
+                separated_list1(line_ending, node),
+            ),
+        )?;
+
+        let indexes: HashMap<&str, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| (*name, index))
+            .collect();
+        if indexes.len() != nodes.len() {
+            bail!("some nodes are defined more than once");
+        }
+        let index = |from: &str, name: &str| {
+            indexes
+                .get(name)
+                .copied()
+                .with_context(|| format!("node {from} leads to {name}, which is not defined"))
+        };
+        let network = nodes
+            .iter()
+            .map(|(name, (left, right))| Ok((index(name, left)?, index(name, right)?)))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            instructions,
+            names: nodes.iter().map(|(name, _)| (*name).to_string()).collect(),
+            network,
+        })
+    }
+
+    fn node(&self, name: &str) -> Result<usize> {
+        self.names
+            .iter()
+            .position(|candidate| candidate == name)
+            .with_context(|| format!("there is no node called {name}"))
+    }
+
+    fn nodes_ending_with(&self, last: char) -> impl Iterator<Item = usize> + '_ {
+        (0..self.names.len()).filter(move |node| self.names[*node].ends_with(last))
+    }
+
+    fn next(&self, node: usize, step: usize) -> usize {
+        let (left, right) = self.network[node];
+        match self.instructions[step % self.instructions.len()] {
+            Instruction::Left => left,
+            Instruction::Right => right,
+        }
+    }
+
+    /// Count the steps from `start` until a node is reached where `arrived` is true.
+    fn steps_until(&self, start: usize, arrived: impl Fn(usize) -> bool) -> Result<u64> {
+        // Once a node is revisited at the same point in the instructions, the walk loops.
+        let mut seen = vec![false; self.names.len() * self.instructions.len()];
+        let mut node = start;
+        for step in 0.. {
+            if arrived(node) {
+                return Ok(step as u64);
+            }
+            let state = node * self.instructions.len() + step % self.instructions.len();
+            if std::mem::replace(&mut seen[state], true) {
+                break;
+            }
+            node = self.next(node, step);
+        }
+        bail!("the path from {} loops forever", self.names[start])
+    }
+
+    /// Walk from `start` until the walk starts repeating, noting when it is on a node ending
+    /// in Z.
+    fn ghost(&self, start: usize) -> Ghost {
+        let mut first_seen = vec![None; self.names.len() * self.instructions.len()];
+        let mut arrivals = Vec::new();
+        let mut node = start;
+        for step in 0.. {
+            let state = node * self.instructions.len() + step % self.instructions.len();
+            if let Some(loop_start) = first_seen[state] {
+                return Ghost {
+                    arrivals,
+                    loop_start,
+                    loop_length: step as u64 - loop_start,
+                };
+            }
+            first_seen[state] = Some(step as u64);
+            if self.names[node].ends_with('Z') {
+                arrivals.push(step as u64);
+            }
+            node = self.next(node, step);
+        }
+        unreachable!("a walk through finitely many states always repeats")
+    }
+}
+
+/// Where one ghost's walk reaches a node ending in Z. From `loop_start` onwards, the walk
+/// repeats every `loop_length` steps.
+#[derive(Debug, PartialEq)]
+struct Ghost {
+    /// The steps, before the walk first repeats, at which the ghost is on a node ending in Z.
+    arrivals: Vec<u64>,
+    loop_start: u64,
+    loop_length: u64,
+}
+
+impl Ghost {
+    fn arrives_at(&self, step: u64) -> bool {
+        if step < self.loop_start {
+            self.arrivals.contains(&step)
+        } else {
+            let in_loop = self.loop_start + (step - self.loop_start) % self.loop_length;
+            self.arrivals.contains(&in_loop)
+        }
+    }
+
+    /// The arrivals that repeat forever.
+    fn looping_arrivals(&self) -> impl Iterator<Item = u64> + '_ {
+        self.arrivals
+            .iter()
+            .copied()
+            .filter(|step| *step >= self.loop_start)
+    }
+}
+
+/// The first step at which every ghost is on a node ending in Z.
+///
+/// Before every ghost has entered its loop, arrivals are checked one at a time. After that,
+/// each ghost arrives on a fixed set of remainders of its loop length, so the first common step
+/// is found with the Chinese remainder theorem instead of walking.
+fn steps_until_all_arrive(ghosts: &[Ghost]) -> Option<u64> {
+    let all_looping = ghosts.iter().map(|ghost| ghost.loop_start).max()?;
+    let first = &ghosts[0];
+    let first_loop_length = usize::try_from(first.loop_length).unwrap_or(usize::MAX);
+    let early = first
+        .arrivals
+        .iter()
+        .copied()
+        .chain(
+            first
+                .looping_arrivals()
+                .flat_map(|arrival| (arrival..all_looping).step_by(first_loop_length)),
+        )
+        .filter(|step| *step < all_looping)
+        .filter(|step| ghosts.iter().all(|ghost| ghost.arrives_at(*step)))
+        .min();
+    if early.is_some() {
+        return early;
+    }
+
+    // Try every combination of looping arrivals, one from each ghost.
+    let mut combinations: Vec<Vec<(u64, u64)>> = vec![Vec::new()];
+    for ghost in ghosts {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                ghost.looping_arrivals().map(move |arrival| {
+                    let mut combination = combination.clone();
+                    combination.push((arrival % ghost.loop_length, ghost.loop_length));
+                    combination
+                })
+            })
+            .collect();
+    }
+    combinations
+        .into_iter()
+        .filter_map(|combination| {
+            let (step, period) = math::chinese_remainder(combination)?;
+            // Every ghost must be inside its loop for the remainders to hold.
+            let behind = all_looping.saturating_sub(step);
+            step.checked_add(behind.div_ceil(period).checked_mul(period)?)
+        })
+        .min()
+}
+
+/// Parse the left and right instructions, like `LLR`.
+fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+    many1(map(one_of("LR"), |c| {
+        if c == 'L' {
+            Instruction::Left
+        } else {
+            Instruction::Right
+        }
+    }))(input)
+}
+
+/// Parse a node and its neighbours, like `AAA = (BBB, CCC)`.
+fn node(input: &str) -> IResult<&str, (&str, (&str, &str))> {
+    separated_pair(
+        alphanumeric1,
+        tag(" = "),
+        delimited(
+            tag("("),
+            map(
+                tuple((alphanumeric1, tag(", "), alphanumeric1)),
+                |(left, _, right)| (left, right),
+            ),
+            tag(")"),
+        ),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    const REPEATING_EXAMPLE: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    const GHOST_EXAMPLE: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn parsing() {
+        let map = Map::parse(REPEATING_EXAMPLE).unwrap();
+        assert_eq!(
+            map.instructions,
+            [Instruction::Left, Instruction::Left, Instruction::Right]
+        );
+        assert_eq!(map.names, ["AAA", "BBB", "ZZZ"]);
+        assert_eq!(map.network, [(1, 1), (0, 2), (2, 2)]);
+
+        let error = Map::parse("LR\n\nAAA = (BBB, AAA)").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "node AAA leads to BBB, which is not defined"
+        );
+        assert!(Map::parse("LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)").is_err());
+        assert!(Map::parse("LX\n\nAAA = (AAA, AAA)").is_err());
+    }
+
+    #[test]
+    fn walking() {
+        let steps = |input| Solution.part1(&Map::parse(input).unwrap());
+        assert_eq!(steps(EXAMPLE).unwrap().to_string(), "2");
+        assert_eq!(steps(REPEATING_EXAMPLE).unwrap().to_string(), "6");
+        let error = steps("L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+        assert_eq!(error.to_string(), "the path from AAA loops forever");
+    }
+
+    #[test]
+    fn ghost_loops() {
+        let map = Map::parse(GHOST_EXAMPLE).unwrap();
+        let ghost = map.ghost(map.node("22A").unwrap());
+        assert_eq!(
+            ghost,
+            Ghost {
+                arrivals: vec![3, 6],
+                loop_start: 1,
+                loop_length: 6,
+            }
+        );
+        assert!(ghost.arrives_at(9));
+        assert!(!ghost.arrives_at(10));
+        assert_eq!(Solution.part2(&map).unwrap().to_string(), "6");
+    }
+
+    #[test]
+    fn ghosts_matching_before_their_loops() {
+        let ghosts = [
+            Ghost {
+                arrivals: vec![2, 5],
+                loop_start: 4,
+                loop_length: 3,
+            },
+            Ghost {
+                arrivals: vec![2, 7],
+                loop_start: 3,
+                loop_length: 5,
+            },
+        ];
+        assert_eq!(steps_until_all_arrive(&ghosts), Some(2));
+    }
+
+    #[test]
+    fn ghosts_with_offset_loops() {
+        // The first ghost arrives at 5, 8, 11, ... and the second at 4, 11, 18, ...
+        let ghosts = [
+            Ghost {
+                arrivals: vec![5],
+                loop_start: 4,
+                loop_length: 3,
+            },
+            Ghost {
+                arrivals: vec![4],
+                loop_start: 1,
+                loop_length: 7,
+            },
+        ];
+        assert_eq!(steps_until_all_arrive(&ghosts), Some(11));
+        let brute_force = (0..100).find(|step| ghosts.iter().all(|ghost| ghost.arrives_at(*step)));
+        assert_eq!(brute_force, Some(11));
+
+        let never = [
+            Ghost {
+                arrivals: vec![2],
+                loop_start: 0,
+                loop_length: 4,
+            },
+            Ghost {
+                arrivals: vec![1],
+                loop_start: 0,
+                loop_length: 2,
+            },
+        ];
+        assert_eq!(steps_until_all_arrive(&never), None);
+    }
+}
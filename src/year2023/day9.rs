@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+
+use crate::math;
+use crate::parse;
+use crate::solver::{Answer, Solver};
+
+#[derive(Default)]
+pub struct Solution;
+
+impl Solver for Solution {
+    /// The history of readings on each line, like `10 13 16 21 30 45`.
+    type Parsed = Vec<Vec<i64>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        parse::lines(input, |line| parse::parse_all(line, parse::signed_list))
+    }
+
+    fn part1(&self, histories: &Self::Parsed) -> Result<Answer> {
+        Ok(sum_predictions(histories, math::extrapolate_next)?.into())
+    }
+
+    fn part2(&self, histories: &Self::Parsed) -> Result<Answer> {
+        Ok(sum_predictions(histories, math::extrapolate_previous)?.into())
+    }
+}
+
+/// Add up the value predicted for each history.
+fn sum_predictions(histories: &[Vec<i64>], predict: fn(&[i64]) -> Option<i64>) -> Result<i64> {
+    histories
+        .iter()
+        .map(|history| predict(history).context("a history is empty"))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    fn histories(input: &str) -> Vec<Vec<i64>> {
+        Solution.parse(input).unwrap()
+    }
+
+    #[test]
+    fn next_values() {
+        assert_eq!(
+            Solution.part1(&histories(EXAMPLE)).unwrap().to_string(),
+            "114"
+        );
+        assert_eq!(
+            sum_predictions(&histories("1 3 6 10 15 21"), math::extrapolate_next).unwrap(),
+            28
+        );
+    }
+
+    #[test]
+    fn previous_values() {
+        assert_eq!(
+            Solution.part2(&histories(EXAMPLE)).unwrap().to_string(),
+            "2"
+        );
+        assert_eq!(
+            sum_predictions(&histories("10 13 16 21 30 45"), math::extrapolate_previous).unwrap(),
+            5
+        );
+    }
+
+    #[test]
+    fn negative_readings() {
+        let readings = histories("-3 -1 1 3\n5 0 -5 -10");
+        assert_eq!(
+            sum_predictions(&readings, math::extrapolate_next).unwrap(),
+            5 - 15
+        );
+        assert_eq!(
+            sum_predictions(&readings, math::extrapolate_previous).unwrap(),
+            -5 + 10
+        );
+        assert!(sum_predictions(&[Vec::new()], math::extrapolate_next).is_err());
+        let error = Solution.parse("1 2 3\n4 - 5").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "line 2: '4 - 5': column 3: unexpected '- 5'"
+        );
+    }
+}